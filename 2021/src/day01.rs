@@ -1,21 +1,29 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::error::Error;
-use std::env;
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path> {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+use aoc_common::error::{parse_at, AocError};
+use aoc_common::solution::{Answer, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input.lines().map(|line| parse_at(input, line)).collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
 }
 
-fn part1(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
+fn part1(input: &[u32]) -> Result<u32, AocError> {
     let mut prev = u32::MAX;
     let mut num_increases = 0;
 
-    for line in input {
-        let line = line?.parse::<u32>().unwrap();
+    for &line in input {
         if line > prev {
             num_increases += 1;
         }
@@ -25,22 +33,24 @@ fn part1(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
     Ok(num_increases)
 }
 
-fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
+fn part2(input: &[u32]) -> Result<u32, AocError> {
     let mut prevs = [u32::MAX; 3];
     let mut prevs_pointer = 0;
     let mut prevs_sum;
-    
+
     let mut num_increases = 0;
 
+    if input.len() < 3 {
+        return Err("Not enough inputs".into());
+    }
+
     // Unroll the first 3 loop iterations to save a branch.
-    prevs[0] = input.next().ok_or("Not enough inputs")??.parse::<u32>().unwrap();
-    prevs[1] = input.next().ok_or("Not enough inputs")??.parse::<u32>().unwrap();
-    prevs[2] = input.next().ok_or("Not enough inputs")??.parse::<u32>().unwrap();
+    prevs[0] = input[0];
+    prevs[1] = input[1];
+    prevs[2] = input[2];
     prevs_sum = prevs[0] + prevs[1] + prevs[2];
 
-    for line in input {
-        let line = line?.parse::<u32>().unwrap();
-
+    for &line in &input[3..] {
         let cur_sum = prevs_sum - prevs[prevs_pointer] + line;
 
         if cur_sum > prevs_sum {
@@ -51,24 +61,6 @@ fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error
         prevs_pointer = (prevs_pointer + 1) % 3;
         prevs_sum = cur_sum;
     }
-    
-    Ok(num_increases)
-}
-
 
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2021!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day01")?;
-    println!("Part 1: Num increases: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day01")?;
-    println!("Part 2: Num increases: {}", part2(lines)?);
-
-    Ok(())
+    Ok(num_increases)
 }
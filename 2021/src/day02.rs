@@ -1,94 +1,95 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::error::Error;
-use std::env;
-use regex::Regex;
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path> {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn part1(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let mut horizontal = 0;
-    let mut depth = 0;
-    let re = Regex::new(r"(\w+) (\d+)").unwrap();
-
-
-    for line in input {
-        let line = line.unwrap();
-        let caps = re.captures(&line).unwrap();
-        let command = caps.get(1).unwrap().as_str();
-        let units = caps.get(2).unwrap().as_str().parse::<u32>()?;
-
-        match command {
-            "forward" => {
-                horizontal += units;
-            }
-            "down" => {
-                depth += units;
-            }
-            "up" => {
-                depth -= units;
-            }
-            _ => {
-                return Err("unsupported command".into());
-            }
-        }
-    }
-
-    Ok(horizontal * depth)
-}
-
-fn part2(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let mut horizontal = 0;
-    let mut depth = 0;
-    let mut aim = 0;
-    let re = Regex::new(r"(\w+) (\d+)").unwrap();
-
-
-    for line in input {
-        let line = line.unwrap();
-        let caps = re.captures(&line).unwrap();
-        let command = caps.get(1).unwrap().as_str();
-        let units = caps.get(2).unwrap().as_str().parse::<u32>()?;
-
-        match command {
-            "forward" => {
-                horizontal += units;
-                depth += aim * units;
-            }
-            "down" => {
-                aim += units;
-            }
-            "up" => {
-                aim -= units;
-            }
-            _ => {
-                return Err("unsupported command".into());
-            }
-        }
-    }
-
-    Ok(horizontal * depth)
-}
-
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2021!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day02")?;
-    println!("Part 1: Num increases: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day02")?;
-    println!("Part 2: Num increases: {}", part2(lines)?);
-
-    Ok(())
-}
+use aoc_common::error::{parse_at, AocError};
+use aoc_common::solution::{Answer, Solution};
+use regex::Regex;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Command>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
+}
+
+pub enum Command {
+    Forward(u32),
+    Down(u32),
+    Up(u32),
+}
+
+fn parse_input(input: &str) -> Result<Vec<Command>, AocError> {
+    let re = Regex::new(r"(\w+) (\d+)").unwrap();
+    let mut commands = Vec::new();
+
+    for line in input.lines() {
+        let caps = re
+            .captures(line)
+            .ok_or_else(|| AocError::at(input, line, "expected a command"))?;
+        let command = caps.get(1).unwrap().as_str();
+        let units = parse_at(input, caps.get(2).unwrap().as_str())?;
+
+        match command {
+            "forward" => commands.push(Command::Forward(units)),
+            "down" => commands.push(Command::Down(units)),
+            "up" => commands.push(Command::Up(units)),
+            _ => {
+                return Err(AocError::at(input, command, "unsupported command"));
+            }
+        }
+    }
+
+    Ok(commands)
+}
+
+fn part1(input: &[Command]) -> Result<u32, AocError> {
+    let mut horizontal = 0;
+    let mut depth = 0;
+
+    for command in input {
+        match command {
+            Command::Forward(units) => {
+                horizontal += units;
+            }
+            Command::Down(units) => {
+                depth += units;
+            }
+            Command::Up(units) => {
+                depth -= units;
+            }
+        }
+    }
+
+    Ok(horizontal * depth)
+}
+
+fn part2(input: &[Command]) -> Result<u32, AocError> {
+    let mut horizontal = 0;
+    let mut depth = 0;
+    let mut aim = 0;
+
+    for command in input {
+        match command {
+            Command::Forward(units) => {
+                horizontal += units;
+                depth += aim * units;
+            }
+            Command::Down(units) => {
+                aim += units;
+            }
+            Command::Up(units) => {
+                aim -= units;
+            }
+        }
+    }
+
+    Ok(horizontal * depth)
+}
@@ -1,164 +1,135 @@
-use std::collections::{VecDeque, HashSet};
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn energize(i: usize, j: usize, field: &mut [[u32; 10]; 10], to_process: &mut VecDeque<(usize, usize)>) {
-    field[i][j] += 1;
-    if field[i][j] == 10 {
-        to_process.push_back((i, j));
-    }
-}
-
-fn parse_input(mut input: io::Lines<io::BufReader<File>>) -> [[u32; 10]; 10] {
-    let mut field = [[0; 10]; 10];
-
-    for i in 0..10 {
-        let line = input.next().unwrap().unwrap();
-        let mut line = line.chars();
-        for j in 0..10 {
-            field[i][j] = line.next().unwrap().to_digit(10).unwrap();
-        }
-    }
-
-    field
-}
-
-fn run_step(field: &mut [[u32; 10]; 10]) {
-    let mut to_process = VecDeque::new();
-
-    for i in 0..10 {
-        for j in 0..10 {
-            energize(i, j, field, &mut to_process);
-        }
-    }
-
-    while let Some((i, j)) = to_process.pop_front() {
-        if i != 0 {
-            energize(i-1, j, field, &mut to_process);
-        }
-        if i != 9 {
-            energize(i+1, j, field, &mut to_process);
-        }
-        if j != 0 {
-            energize(i, j-1, field, &mut to_process);
-        }
-        if j != 9 {
-            energize(i, j+1, field, &mut to_process);
-        }
-
-        if i != 0 && j != 0 {
-            energize(i-1, j-1, field, &mut to_process);
-        }
-        if i != 0 && j != 9 {
-            energize(i-1, j+1, field, &mut to_process);
-        }
-        if i != 9 && j != 0 {
-            energize(i+1, j-1, field, &mut to_process);
-        }
-        if i != 9 && j != 9 {
-            energize(i+1, j+1, field, &mut to_process);
-        }
-    }
-}
-
-fn part1(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let mut field = parse_input(input);
-
-    let mut flashes = 0;
-    
-    for step in 1..101 {
-        run_step(&mut field);        
-
-        for i in 0..10 {
-            for j in 0..10 {
-                if field[i][j] > 9 {
-                    field[i][j] = 0;
-                    flashes += 1;
-                }
-            }
-        }
-
-        println!("After step {}", step);
-        for i in 0..10 {
-            for j in 0..10 {
-                print!("{}", field[i][j]);
-            }
-            println!()
-        }
-    }
-
-    println!("Flashes: {}", flashes);
-    Ok(0)
-}
-
-fn part2(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let mut field = parse_input(input);
-
-    let mut step = 1;
-
-    loop {
-        run_step(&mut field);        
-
-        let mut all_flashing = true;
-        for i in 0..10 {
-            for j in 0..10 {
-                if field[i][j] > 9 {
-                    field[i][j] = 0;
-                } else {
-                    all_flashing = false;
-                }
-            }
-        }
-
-        println!("After step {}", step);
-        for i in 0..10 {
-            for j in 0..10 {
-                print!("{}", field[i][j]);
-            }
-            println!()
-        }
-
-        if all_flashing {
-            break;
-        }
-
-        step += 1;
-    }
-
-    println!("Step: {}", step);
-    Ok(step)
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2021!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day11")?;
-    println!("Part 1: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day11")?;
-    println!("Part 2: {}", part2(lines)?);
-
-    Ok(())
-}
-
-
-struct Graph {
-    // v: HashSet<Node>,
-    v: HashSet<u32>,
-}
\ No newline at end of file
+use aoc_common::error::AocError;
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::simulation::{run_to_end, Simulation};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::visualize::{self, Rgb, BLUE, NAVY, WHITE};
+use log::{debug, trace};
+use std::collections::VecDeque;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Grid::parse(input, |c| c.to_digit(10))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input.clone())?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input.clone())?.into())
+    }
+
+    fn simulation(input: &Self::Input, part: u32) -> Result<Box<dyn Simulation + '_>, AocError> {
+        Ok(Box::new(Octopuses::new(input.clone(), part)))
+    }
+}
+
+fn energize(pos: Pos, field: &mut Grid<u32>, to_process: &mut VecDeque<Pos>) {
+    field[pos] += 1;
+    if field[pos] == 10 {
+        to_process.push_back(pos);
+    }
+}
+
+fn run_step(field: &mut Grid<u32>) {
+    let mut to_process = VecDeque::new();
+
+    for pos in field.positions() {
+        energize(pos, field, &mut to_process);
+    }
+
+    while let Some(pos) = to_process.pop_front() {
+        for n in field.neighbors8(pos).collect::<Vec<_>>() {
+            energize(n, field, &mut to_process);
+        }
+    }
+}
+
+// Octopuses that just flashed are white, the others get brighter as their energy rises.
+fn draw(field: &Grid<u32>) -> Grid<Rgb> {
+    field.map(|&energy| match energy {
+        0 => WHITE,
+        energy => visualize::blend(NAVY, BLUE, f64::from(energy) / 9.0),
+    })
+}
+
+// Part 1 stops after 100 steps, part 2 once all octopuses flash at the same time.
+struct Octopuses {
+    field: Grid<u32>,
+    part: u32,
+    step: u32,
+    flashes: u32,
+    synchronized: bool,
+}
+
+impl Octopuses {
+    fn new(field: Grid<u32>, part: u32) -> Octopuses {
+        Octopuses {
+            field,
+            part,
+            step: 0,
+            flashes: 0,
+            synchronized: false,
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        match self.part {
+            1 => self.step == 100,
+            _ => self.synchronized,
+        }
+    }
+}
+
+impl Simulation for Octopuses {
+    fn step(&mut self) -> bool {
+        if self.is_over() {
+            return false;
+        }
+
+        run_step(&mut self.field);
+        self.step += 1;
+
+        self.synchronized = true;
+        for pos in self.field.positions() {
+            if self.field[pos] > 9 {
+                self.field[pos] = 0;
+                self.flashes += 1;
+            } else {
+                self.synchronized = false;
+            }
+        }
+
+        trace!("After step {}:\n{}", self.step, self.field);
+        visualize::frame(|| draw(&self.field));
+
+        true
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "step {}, {} flashes\n\n{}",
+            self.step, self.flashes, self.field
+        )
+    }
+}
+
+fn part1(field: Grid<u32>) -> Result<u32, AocError> {
+    let mut octopuses = Octopuses::new(field, 1);
+    run_to_end(&mut octopuses);
+
+    debug!("Flashes: {}", octopuses.flashes);
+    Ok(octopuses.flashes)
+}
+
+fn part2(field: Grid<u32>) -> Result<u32, AocError> {
+    let mut octopuses = Octopuses::new(field, 2);
+    run_to_end(&mut octopuses);
+
+    debug!("Step: {}", octopuses.step);
+    Ok(octopuses.step)
+}
@@ -1,205 +1,165 @@
-use std::cell::RefCell;
-use std::collections::{HashSet, VecDeque, HashMap};
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::hash::{Hash, Hasher};
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::rc::Rc;
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-struct Graph {
-    v: HashMap<String, Rc<Node>>,
-}
-
-impl Graph {
-    fn new() -> Graph {       
-        Graph { v: HashMap::new() }
-    }
-
-    fn contains(&self, name: &str) -> bool {
-        self.v.contains_key(name)
-    }
-
-    fn get_node(&self, name: &str) -> Rc<Node> {
-        Rc::clone(&self.v[name])
-    }
-
-    fn add_node(&mut self, name: &str) {
-        self.v.insert(name.into(), Rc::new(Node::new(name.into())));
-    }
-
-    fn add_edge(&self, v1: Rc<Node>, v2: Rc<Node>) {
-        v1.neighbors.borrow_mut().insert(Rc::clone(&v2));
-        v2.neighbors.borrow_mut().insert(Rc::clone(&v1));
-    }
-}
-
-#[derive(Eq)]
-struct Node {
-    name: String,
-    neighbors: RefCell<HashSet<Rc<Node>>>,
-}
-
-impl Node {
-    fn new(name: String) -> Node {
-        Node {
-            name: name,
-            neighbors: RefCell::new(HashSet::new()),
-        }
-    }
-}
-
-impl PartialEq for Node {
-    fn eq(&self, rhs: &Self) -> bool {
-        self.name == rhs.name
-    }
-}
-
-// TODO: Working with Nodes rather than just the name strings directly seems pointless. Remove this.
-impl Hash for Node {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.name.hash(state);
-    }
-}
-
-fn parse_input<'a>(input: io::Lines<io::BufReader<File>>) -> Result<Graph, Box<dyn Error>> {
-    let mut graph = Graph::new();
-
-    for line in input {
-        let line = line?;
-        let mut line = line.split('-');
-
-        let v1 = line.next().unwrap();
-        let v2 = line.next().unwrap();
-
-        if !graph.contains(v1) {
-            graph.add_node(v1);
-        }
-
-        if !graph.contains(v2) {
-            graph.add_node(v2);
-        }
-
-        let v1_node = graph.get_node(v1);
-        let v2_node = graph.get_node(v2);
-
-        graph.add_edge(v1_node, v2_node);
-    }
-
-    Ok(graph)
-}
-
-struct PathInfo<'a> {
-    path_so_far: &'a Vec<String>,
-    small_cave_joker_used: bool,
-}
-
-fn is_small_cave(n: &Rc<Node>) -> bool {
-    ('a'..='z').contains(&n.name.chars().next().unwrap())
-}
-
-fn is_node_eligible_part1(path_info: &PathInfo, n: &Rc<Node>) -> bool {
-    if is_small_cave(n) && path_info.path_so_far.contains(&n.name) {
-        return false;
-    }
-    true
-}
-
-fn is_node_eligible_part2(path_info: &PathInfo, n: &Rc<Node>) -> bool {
-    if path_info.small_cave_joker_used
-        && is_small_cave(n)
-        && path_info.path_so_far.contains(&n.name)
-    {
-        return false;
-    }
-    true
-}
-
-fn paths(
-    g: &Graph,
-    path_info: &PathInfo,
-    current_node: Rc<Node>,
-    is_node_eligible: fn(&PathInfo, &Rc<Node>) -> bool,
-) -> Vec<Vec<String>> {
-    let mut p = Vec::new();
-
-    for n in &*current_node.neighbors.borrow() {
-        if n.name == "start" {
-            continue;
-        }
-        if !is_node_eligible(&path_info, n) {
-            continue;
-        }
-
-        let mut new_path = path_info.path_so_far.clone();
-        new_path.push(n.name.clone());
-
-        let new_path_info = PathInfo {
-            path_so_far: &new_path,
-            small_cave_joker_used: path_info.small_cave_joker_used || (is_small_cave(n) && path_info.path_so_far.contains(&n.name)),
-        };
-
-        if n.name == "end" {
-            p.push(new_path);
-        } else {
-            p.append(&mut paths(
-                g,
-                &new_path_info,
-                Rc::clone(n),
-                is_node_eligible,
-            ));
-        }
-    }
-
-    p
-}
-
-fn part1(
-    input: io::Lines<io::BufReader<File>>,
-    is_node_eligible: fn(&PathInfo, &Rc<Node>) -> bool,
-) -> Result<usize, Box<dyn Error>> {
-    let graph = parse_input(input)?;
-    let start_node = graph.get_node("start");
-
-    let mut start_path = Vec::new();
-    start_path.push("start".into());
-
-    let path_info = PathInfo {
-        path_so_far: &start_path,
-        small_cave_joker_used: false,
-    };
-
-    let p = paths(&graph, &path_info, start_node, is_node_eligible);
-
-    // for path in &p {
-    //     println!("{:?}", path);
-    // }
-
-    Ok(p.len())
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2021!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day12")?;
-    println!("Part 1: {}", part1(lines, is_node_eligible_part1)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day12")?;
-    println!("Part 2: {}", part1(lines, is_node_eligible_part2)?);
-
-    Ok(())
-}
+use aoc_common::dot::Dot;
+use aoc_common::error::AocError;
+use aoc_common::search;
+use aoc_common::solution::{Answer, Solution};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Graph;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(count_paths(input, is_node_eligible_part1)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(count_paths(input, is_node_eligible_part2)?.into())
+    }
+
+    fn dot(input: &Self::Input) -> Result<Dot, AocError> {
+        Ok(input.dot())
+    }
+}
+
+pub struct Graph {
+    // Caves are referred to by their index in here.
+    names: Vec<String>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    fn new() -> Graph {
+        Graph {
+            names: Vec::new(),
+            neighbors: Vec::new(),
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    fn add_node(&mut self, name: &str) -> usize {
+        self.find(name).unwrap_or_else(|| {
+            self.names.push(name.into());
+            self.neighbors.push(Vec::new());
+            self.names.len() - 1
+        })
+    }
+
+    fn add_edge(&mut self, v1: usize, v2: usize) {
+        self.neighbors[v1].push(v2);
+        self.neighbors[v2].push(v1);
+    }
+
+    fn is_small_cave(&self, cave: usize) -> bool {
+        self.names[cave].starts_with(|c: char| c.is_ascii_lowercase())
+    }
+
+    // Small caves are drawn as ellipses and big ones as boxes, start and end stand out.
+    fn dot(&self) -> Dot {
+        let mut dot = Dot::graph();
+
+        for (cave, name) in self.names.iter().enumerate() {
+            let (label, shape) = match name.as_str() {
+                "start" | "end" => (name.clone(), "doublecircle"),
+                _ if self.is_small_cave(cave) => (format!("{}\nsmall", name), "ellipse"),
+                _ => (format!("{}\nbig", name), "box"),
+            };
+            dot.node(cave, &[("label", &label), ("shape", shape)]);
+        }
+
+        for (cave, neighbors) in self.neighbors.iter().enumerate() {
+            // Every edge is stored in both directions.
+            for &neighbor in neighbors.iter().filter(|&&n| n > cave) {
+                dot.edge(cave, neighbor, &[]);
+            }
+        }
+
+        dot
+    }
+}
+
+fn parse_input(input: &str) -> Result<Graph, AocError> {
+    let mut graph = Graph::new();
+
+    for line in input.lines() {
+        let (v1, v2) = line
+            .split_once('-')
+            .ok_or_else(|| AocError::at(input, line, "expected \"a-b\""))?;
+
+        let v1 = graph.add_node(v1);
+        let v2 = graph.add_node(v2);
+        graph.add_edge(v1, v2);
+    }
+
+    // Visited small caves are tracked as bits of a u64.
+    if graph.names.len() > 64 {
+        return Err("too many caves".into());
+    }
+
+    Ok(graph)
+}
+
+// A path through the caves so far, reduced to what decides where it can go next.
+#[derive(Clone, Copy)]
+struct PathInfo {
+    cave: usize,
+    small_caves_visited: u64,
+    small_cave_joker_used: bool,
+}
+
+impl PathInfo {
+    fn visited(&self, cave: usize) -> bool {
+        self.small_caves_visited & (1 << cave) != 0
+    }
+}
+
+fn is_node_eligible_part1(graph: &Graph, path_info: &PathInfo, n: usize) -> bool {
+    !(graph.is_small_cave(n) && path_info.visited(n))
+}
+
+fn is_node_eligible_part2(graph: &Graph, path_info: &PathInfo, n: usize) -> bool {
+    !(path_info.small_cave_joker_used && graph.is_small_cave(n) && path_info.visited(n))
+}
+
+fn count_paths(
+    graph: &Graph,
+    is_node_eligible: fn(&Graph, &PathInfo, usize) -> bool,
+) -> Result<usize, AocError> {
+    let start = graph.find("start").ok_or("no start cave")?;
+    let end = graph.find("end").ok_or("no end cave")?;
+
+    let start_path = PathInfo {
+        cave: start,
+        small_caves_visited: 1 << start,
+        small_cave_joker_used: false,
+    };
+
+    Ok(search::count_paths(
+        start_path,
+        |path_info| {
+            let path_info = *path_info;
+
+            graph.neighbors[path_info.cave]
+                .iter()
+                .filter(move |&&n| n != start && is_node_eligible(graph, &path_info, n))
+                .map(move |&n| {
+                    let small = graph.is_small_cave(n);
+
+                    PathInfo {
+                        cave: n,
+                        small_caves_visited: path_info.small_caves_visited
+                            | if small { 1 << n } else { 0 },
+                        small_cave_joker_used: path_info.small_cave_joker_used
+                            || (small && path_info.visited(n)),
+                    }
+                })
+        },
+        |path_info| path_info.cave == end,
+    ))
+}
@@ -1,226 +1,204 @@
-use regex::Regex;
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::ops::Index;
-use std::ops::IndexMut;
-use std::path::Path;
-
-// Maybe a type alias would've been better.
-struct Board {
-    data: Vec<Vec<char>>,
-    x_min: usize,
-    x_max: usize,
-    y_min: usize,
-    y_max: usize,
-}
-
-impl Board {
-    fn new(x_max: usize, y_max: usize) -> Board {
-        Board {
-            data: vec![vec!['.'; x_max]; y_max],
-            x_min: 0,
-            x_max: 0,
-            y_min: 0,
-            y_max: 0,
-        }
-    }
-
-    fn resize(&mut self, new_x: usize, new_y: usize) {
-        for i in 0..=new_x {
-            self.data[i].resize(new_y + 1, 'f');
-        }
-        self.data.resize(new_x + 1, Vec::new());
-    }
-
-    fn print(&self) {
-        println!("x: {} to {}", self.x_min, self.x_max);
-        println!("y: {} to {}", self.y_min, self.y_max);
-        for y in self.y_min..=self.y_max {
-            for x in self.x_min..=self.x_max {
-                print!("{}", self.data[x][y]);
-            }
-            println!("");
-        }
-    }
-}
-
-impl Index<usize> for Board {
-    type Output = Vec<char>;
-
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.data[index]
-    }
-}
-
-impl IndexMut<usize> for Board {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.data[index]
-    }
-}
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn parse_input(input: &mut io::Lines<io::BufReader<File>>) -> Result<Board, Box<dyn Error>> {
-    let mut board = Board::new(1500, 1500);
-
-    while let Some(line) = input.next() {
-        let line = line?;
-
-        if line == "" {
-            break;
-        }
-
-        let mut foo = line.split(',').map(|s| s.parse::<usize>());
-
-        let x = foo.next().unwrap()?;
-        let y = foo.next().unwrap()?;
-
-        if x > board.x_max {
-            board.x_max = x;
-        }
-        if y > board.y_max {
-            board.y_max = y;
-        }
-
-        board[x][y] = '#';
-    }
-
-    board.resize(board.x_max, board.y_max);
-
-    Ok(board)
-}
-
-fn run_step(
-    board: &mut Board,
-    line: &str,
-) -> Result<(), Box<dyn Error>> {
-    let re = Regex::new(r"^fold along (\w)=(\d+)$")?;
-
-    let c = re.captures(&line).unwrap();
-
-    match &c[1] {
-        "x" => {
-            let fold = c[2].parse::<usize>()?;
-
-            if fold < (board.x_max - board.x_min) / 2 {
-                // fold to the right
-                for i in board.x_min..fold {
-                    for y in board.y_min..=board.y_max {
-                        if board[i][y] == '#' {
-                            board[fold + fold - i][y] = '#';
-                        }
-                    }
-                }
-                board.x_min = fold + 1;
-            } else {
-                // fold to the left
-                for i in (fold + 1)..=board.x_max {
-                    for y in board.y_min..=board.y_max {
-                        if board[i][y] == '#' {
-                            board[fold - (i - fold)][y] = '#';
-                        }
-                    }
-                }
-                board.x_max = fold - 1;
-            }
-        }
-        "y" => {
-            let fold = c[2].parse::<usize>()?;
-
-            if fold < (board.y_max - board.y_min) / 2 {
-                // fold to the bottom
-                for i in board.y_min..fold {
-                    for x in board.x_min..=board.x_max {
-                        if board[x][i] == '#' {
-                            board[x][fold + fold - i] = '#';
-                        }
-                    }
-                }
-                board.y_min = fold + 1;
-            } else {
-                // fold to the top
-                for i in (fold + 1)..=board.y_max {
-                    for x in board.x_min..=board.x_max {
-                        if board[x][i] == '#' {
-                            board[x][fold - (i - fold)] = '#';
-                        }
-                    }
-                }
-                board.y_max = fold - 1;
-            }
-        }
-        _ => panic!("unexpected character on fold along line"),
-    }
-
-    Ok(())
-}
-fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let mut board = parse_input(&mut input)?;
-
-    board.print();
-
-    let line = input.next().unwrap()?;
-    run_step(&mut board, &line)?;
-
-    println!("step done");
-    board.print();
-
-    // }
-
-    Ok(count_dots(&board))
-}
-
-fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<(), Box<dyn Error>> {
-    let mut board = parse_input(&mut input)?;
-
-    board.print();
-
-    while let Some(line) = input.next() {
-        run_step(&mut board, &line?)?;
-
-        println!("step done");
-        board.print();
-    }
-
-    Ok(())
-}
-
-fn count_dots(board: &Board) -> u32 {
-    let mut num = 0;
-
-    for y in board.y_min..=board.y_max {
-        for x in board.x_min..=board.x_max {
-            if board[x][y] == '#' {
-                num += 1;
-            }
-        }
-    }
-
-    num
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2021!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day13")?;
-    println!("Part 1: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day13")?;
-    println!("Part 2:");
-    part2(lines)?;
-
-    Ok(())
-}
+use aoc_common::error::{parse_at, AocError};
+use aoc_common::grid::Grid;
+use aoc_common::ocr;
+use aoc_common::simulation::{run_to_end, Simulation};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::sparse::SparseGrid;
+use aoc_common::visualize::{self, Rgb, NAVY, YELLOW};
+use log::trace;
+use regex::Regex;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = (Board, Vec<Fold>);
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
+
+    fn simulation(input: &Self::Input, part: u32) -> Result<Box<dyn Simulation + '_>, AocError> {
+        Ok(Box::new(Paper::new(input, part)))
+    }
+}
+
+pub enum Fold {
+    X(i64),
+    Y(i64),
+}
+
+#[derive(Clone)]
+pub struct Board {
+    dots: SparseGrid<char>,
+    // The paper can have empty rows and columns at its edges, so its size isn't simply the
+    // bounding box of the dots.
+    width: i64,
+    height: i64,
+}
+
+impl Board {
+    fn trace(&self) {
+        trace!("{}x{}\n{}", self.width, self.height, self.render());
+        visualize::frame(|| self.draw());
+    }
+
+    fn draw(&self) -> Grid<Rgb> {
+        visualize::draw_sparse(
+            &self.dots,
+            (0, 0),
+            (self.width - 1, self.height - 1),
+            |_, dot| if dot.is_some() { YELLOW } else { NAVY },
+        )
+    }
+
+    fn render(&self) -> String {
+        self.dots
+            .render_area((0, 0), (self.width - 1, self.height - 1))
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Board, Vec<Fold>), AocError> {
+    let mut dots = SparseGrid::new();
+    let mut lines = input.lines();
+
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+
+        let (x, y) = line
+            .split_once(',')
+            .ok_or_else(|| AocError::at(input, line, "expected \"x,y\""))?;
+        let x: u32 = parse_at(input, x)?;
+        let y: u32 = parse_at(input, y)?;
+
+        dots.insert((x.into(), y.into()), '#');
+    }
+
+    let (_, (x_max, y_max)) = dots.bounds().ok_or("no dots")?;
+    let board = Board {
+        dots,
+        width: x_max + 1,
+        height: y_max + 1,
+    };
+
+    let re = Regex::new(r"^fold along (\w)=(\d+)$").unwrap();
+    let mut folds = Vec::new();
+
+    for line in lines {
+        let c = re
+            .captures(line)
+            .ok_or_else(|| AocError::at(input, line, "expected \"fold along x=N\""))?;
+        let axis = c.get(1).unwrap().as_str();
+        let fold = parse_at(input, c.get(2).unwrap().as_str())?;
+
+        folds.push(match axis {
+            "x" => Fold::X(fold),
+            "y" => Fold::Y(fold),
+            _ => return Err(AocError::at(input, axis, "unexpected axis")),
+        });
+    }
+
+    Ok((board, folds))
+}
+
+fn run_step(board: &mut Board, fold: &Fold) {
+    // Dots beyond the fold line are mirrored onto the other half, where they may land on a dot
+    // that is already there.
+    board.dots = board
+        .dots
+        .iter()
+        .map(|((x, y), &c)| match *fold {
+            Fold::X(fold) if x > fold => ((2 * fold - x, y), c),
+            Fold::Y(fold) if y > fold => ((x, 2 * fold - y), c),
+            _ => ((x, y), c),
+        })
+        .collect();
+
+    match *fold {
+        Fold::X(fold) => board.width = fold,
+        Fold::Y(fold) => board.height = fold,
+    }
+}
+
+// Part 1 only does the first fold.
+struct Paper<'a> {
+    board: Board,
+    folds: &'a [Fold],
+    done: usize,
+}
+
+impl<'a> Paper<'a> {
+    fn new((board, folds): &'a (Board, Vec<Fold>), part: u32) -> Paper<'a> {
+        let board = board.clone();
+        board.trace();
+
+        Paper {
+            board,
+            folds: if part == 1 {
+                &folds[..folds.len().min(1)]
+            } else {
+                folds
+            },
+            done: 0,
+        }
+    }
+}
+
+impl Simulation for Paper<'_> {
+    fn step(&mut self) -> bool {
+        let Some(fold) = self.folds.get(self.done) else {
+            return false;
+        };
+
+        run_step(&mut self.board, fold);
+        self.done += 1;
+
+        trace!("step done");
+        self.board.trace();
+
+        true
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "{} of {} folds, {} dots\n\n{}",
+            self.done,
+            self.folds.len(),
+            count_dots(&self.board),
+            self.board.render()
+        )
+    }
+}
+
+fn part1(input: &(Board, Vec<Fold>)) -> Result<usize, AocError> {
+    if input.1.is_empty() {
+        return Err("no folds".into());
+    }
+
+    let mut paper = Paper::new(input, 1);
+    run_to_end(&mut paper);
+
+    Ok(count_dots(&paper.board))
+}
+
+fn part2(input: &(Board, Vec<Fold>)) -> Result<String, AocError> {
+    let mut paper = Paper::new(input, 2);
+    run_to_end(&mut paper);
+
+    // The example folds into a square rather than letters, keep the picture for that.
+    let picture = paper.board.render();
+    Ok(ocr::read_letters(&picture).unwrap_or(picture))
+}
+
+fn count_dots(board: &Board) -> usize {
+    board.dots.len()
+}
@@ -1,202 +1,224 @@
-use regex::Regex;
-use std::collections::HashMap;
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-struct PolymerState {
-    polymer: String,
-    rules: HashMap<(char, char), char>,
-}
-
-fn parse_input(input: &mut io::Lines<io::BufReader<File>>) -> PolymerState {
-    let polymer = input.next().unwrap().unwrap();
-
-    let line = input.next().unwrap().unwrap();
-    if line != "" {
-        panic!("malformed input");
-    }
-
-    let mut rules = HashMap::new();
-
-    let re = Regex::new(r"^(\w)(\w) -> (\w)$").unwrap();
-
-    for line in input {
-        let line = line.unwrap();
-
-        let c = re.captures(&line).unwrap();
-        rules.insert(
-            (
-                c.get(1).unwrap().as_str().chars().next().unwrap(),
-                c.get(2).unwrap().as_str().chars().next().unwrap(),
-            ),
-            c.get(3).unwrap().as_str().chars().next().unwrap(),
-        );
-    }
-
-    PolymerState { polymer, rules }
-}
-
-fn run_step(state: &PolymerState) -> String {
-    let mut new_polymer = String::with_capacity((state.polymer.len() - 1) * 2);
-    let mut bases = state.polymer.chars();
-
-    let mut prev_base = bases.next().unwrap();
-    new_polymer.push(prev_base);
-
-    for base in bases {
-        if let Some(insert) = state.rules.get(&(prev_base, base)) {
-            new_polymer.push(*insert);
-        }
-
-        new_polymer.push(base);
-        prev_base = base;
-    }
-
-    new_polymer
-}
-
-fn count_bases(state: &PolymerState) -> HashMap<char, u32> {
-    let mut counts = HashMap::new();
-
-    for base in state.polymer.chars() {
-        *counts.entry(base).or_insert(0) += 1;
-    }
-
-    counts
-}
-
-fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let mut state = parse_input(&mut input);
-
-    for _ in 0..10 {
-        state.polymer = run_step(&state);
-        println!("{}", state.polymer);
-    }
-
-    let counts = count_bases(&state);
-
-    let min = counts.iter().min_by_key(|(_, &v)| v).unwrap();
-    let max = counts.iter().max_by_key(|(_, &v)| v).unwrap();
-
-    println!("max: {:?}", max);
-    println!("min: {:?}", min);
-    Ok(max.1 - min.1)
-}
-
-// Part 2
-
-#[derive(Debug)]
-struct PolymerState2 {
-    bases: HashMap<char, u64>,
-    basepairs: HashMap<(char, char), u64>,
-    rules: HashMap<(char, char), char>,
-}
-
-fn parse_input2(input: &mut io::Lines<io::BufReader<File>>) -> PolymerState2 {
-    let mut bases = HashMap::new();
-    let mut basepairs = HashMap::new();
-
-    let polymer = input.next().unwrap().unwrap();
-    let mut chars = polymer.chars();
-    let mut prev_base = chars.next().unwrap();
-    bases.insert(prev_base, 1);
-
-    for base in chars {
-        *bases.entry(base).or_insert(0) += 1;
-        *basepairs.entry((prev_base, base)).or_insert(0) += 1;
-        prev_base = base;
-    }
-
-    let line = input.next().unwrap().unwrap();
-    if line != "" {
-        panic!("malformed input");
-    }
-
-    let mut rules = HashMap::new();
-
-    let re = Regex::new(r"^(\w)(\w) -> (\w)$").unwrap();
-
-    for line in input {
-        let line = line.unwrap();
-
-        let c = re.captures(&line).unwrap();
-        rules.insert(
-            (
-                c.get(1).unwrap().as_str().chars().next().unwrap(),
-                c.get(2).unwrap().as_str().chars().next().unwrap(),
-            ),
-            c.get(3).unwrap().as_str().chars().next().unwrap(),
-        );
-    }
-
-    PolymerState2 {
-        bases,
-        basepairs,
-        rules,
-    }
-}
-
-fn run_step2(state: &PolymerState2) -> PolymerState2 {
-    let mut new_state = PolymerState2 {
-        bases: state.bases.clone(),
-        basepairs: HashMap::new(),
-        rules: state.rules.clone(),
-    };
-
-    for (&(base1, base2), &v) in &state.basepairs {
-        if let Some(&insert) = state.rules.get(&(base1, base2)) {
-            *new_state.bases.entry(insert).or_insert(0) += v;
-
-            *new_state.basepairs.entry((base1, insert)).or_insert(0) += v;
-            *new_state.basepairs.entry((insert, base2)).or_insert(0) += v;
-        } else {
-            *new_state.basepairs.entry((base1, base2)).or_insert(0) += v;
-        }
-    }
-
-    new_state
-}
-
-fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<u64, Box<dyn Error>> {
-    let mut state = parse_input2(&mut input);
-
-    for _ in 0..40 {
-        state = run_step2(&state);
-        println!("{:#?}", state.bases);
-    }
-
-    let min = state.bases.iter().min_by_key(|&(_, v)| v).unwrap();
-    let max = state.bases.iter().max_by_key(|&(_, v)| v).unwrap();
-
-    println!("max: {:?}", max);
-    println!("min: {:?}", min);
-    Ok(max.1 - min.1)
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2021!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day14")?;
-    println!("Part 1: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day14")?;
-    println!("Part 2: {}", part2(lines)?);
-
-    Ok(())
-}
+use aoc_common::error::AocError;
+use aoc_common::random::Rng;
+use aoc_common::solution::{Answer, Solution};
+use log::{debug, trace};
+use regex::Regex;
+use std::collections::HashMap;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = PolymerState;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, AocError> {
+        Ok(generate(rng, size))
+    }
+
+    // The polymer grows exponentially, only part 1 can be built letter by letter.
+    fn reference(input: &Self::Input, part: u32) -> Option<Result<Answer, AocError>> {
+        match part {
+            1 => Some(part1_by_letters(input).map(Answer::from)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PolymerState {
+    polymer: String,
+    rules: HashMap<(char, char), char>,
+}
+
+fn parse_input(input: &str) -> Result<PolymerState, AocError> {
+    let mut lines = input.lines();
+    let polymer = lines.next().ok_or("empty input")?.to_string();
+    if polymer.is_empty() {
+        return Err(AocError::at(input, input, "expected a polymer template"));
+    }
+
+    let line = lines.next().ok_or("no insertion rules")?;
+    if !line.is_empty() {
+        return Err(AocError::at(
+            input,
+            line,
+            "expected a blank line after the template",
+        ));
+    }
+
+    let mut rules = HashMap::new();
+
+    let re = Regex::new(r"^(\w)(\w) -> (\w)$").unwrap();
+
+    for line in lines {
+        let c = re
+            .captures(line)
+            .ok_or_else(|| AocError::at(input, line, "expected \"AB -> C\""))?;
+        rules.insert(
+            (
+                c.get(1).unwrap().as_str().chars().next().unwrap(),
+                c.get(2).unwrap().as_str().chars().next().unwrap(),
+            ),
+            c.get(3).unwrap().as_str().chars().next().unwrap(),
+        );
+    }
+
+    Ok(PolymerState { polymer, rules })
+}
+
+// A template `size` elements long made of a handful of letters, with a rule for every pair of
+// them like the real inputs have.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut letters: Vec<char> = ('A'..='Z').collect();
+    rng.shuffle(&mut letters);
+    letters.truncate(rng.range(2..=10) as usize);
+
+    let mut input: String = (0..size.max(2)).map(|_| *rng.choose(&letters)).collect();
+    input.push('\n');
+
+    for &first in &letters {
+        for &second in &letters {
+            let insert = rng.choose(&letters);
+            input.push_str(&format!("\n{}{} -> {}", first, second, insert));
+        }
+    }
+    input.push('\n');
+
+    input
+}
+
+fn run_step(state: &PolymerState) -> String {
+    let mut new_polymer = String::with_capacity((state.polymer.len() - 1) * 2);
+    let mut bases = state.polymer.chars();
+
+    let mut prev_base = bases.next().unwrap();
+    new_polymer.push(prev_base);
+
+    for base in bases {
+        if let Some(insert) = state.rules.get(&(prev_base, base)) {
+            new_polymer.push(*insert);
+        }
+
+        new_polymer.push(base);
+        prev_base = base;
+    }
+
+    new_polymer
+}
+
+fn count_bases(state: &PolymerState) -> HashMap<char, u32> {
+    let mut counts = HashMap::new();
+
+    for base in state.polymer.chars() {
+        *counts.entry(base).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+// Builds the whole polymer, which only works for a few steps.
+fn part1_by_letters(state: &PolymerState) -> Result<u32, AocError> {
+    let mut state = state.clone();
+
+    for _ in 0..10 {
+        state.polymer = run_step(&state);
+        trace!("{}", state.polymer);
+    }
+
+    let counts = count_bases(&state);
+
+    let min = counts.iter().min_by_key(|(_, &v)| v).unwrap();
+    let max = counts.iter().max_by_key(|(_, &v)| v).unwrap();
+
+    debug!("max: {:?}", max);
+    debug!("min: {:?}", min);
+    Ok(max.1 - min.1)
+}
+
+// Part 2, counting pairs of elements is fast enough for part 1 as well.
+
+#[derive(Debug)]
+struct PolymerState2 {
+    bases: HashMap<char, u64>,
+    basepairs: HashMap<(char, char), u64>,
+    rules: HashMap<(char, char), char>,
+}
+
+fn count_pairs(state: &PolymerState) -> PolymerState2 {
+    let mut bases = HashMap::new();
+    let mut basepairs = HashMap::new();
+
+    let mut chars = state.polymer.chars();
+    let mut prev_base = chars.next().unwrap();
+    bases.insert(prev_base, 1);
+
+    for base in chars {
+        *bases.entry(base).or_insert(0) += 1;
+        *basepairs.entry((prev_base, base)).or_insert(0) += 1;
+        prev_base = base;
+    }
+
+    PolymerState2 {
+        bases,
+        basepairs,
+        rules: state.rules.clone(),
+    }
+}
+
+fn run_step2(state: &PolymerState2) -> PolymerState2 {
+    let mut new_state = PolymerState2 {
+        bases: state.bases.clone(),
+        basepairs: HashMap::new(),
+        rules: state.rules.clone(),
+    };
+
+    for (&(base1, base2), &v) in &state.basepairs {
+        if let Some(&insert) = state.rules.get(&(base1, base2)) {
+            *new_state.bases.entry(insert).or_insert(0) += v;
+
+            *new_state.basepairs.entry((base1, insert)).or_insert(0) += v;
+            *new_state.basepairs.entry((insert, base2)).or_insert(0) += v;
+        } else {
+            *new_state.basepairs.entry((base1, base2)).or_insert(0) += v;
+        }
+    }
+
+    new_state
+}
+
+// The most common minus the least common element after `steps` steps, counting pairs of
+// elements instead of building the polymer.
+fn run_steps(state: &PolymerState, steps: usize) -> u64 {
+    let mut state = count_pairs(state);
+
+    for _ in 0..steps {
+        state = run_step2(&state);
+        trace!("{:#?}", state.bases);
+    }
+
+    let min = state.bases.iter().min_by_key(|&(_, v)| v).unwrap();
+    let max = state.bases.iter().max_by_key(|&(_, v)| v).unwrap();
+
+    debug!("max: {:?}", max);
+    debug!("min: {:?}", min);
+    max.1 - min.1
+}
+
+fn part1(state: &PolymerState) -> Result<u64, AocError> {
+    Ok(run_steps(state, 10))
+}
+
+fn part2(state: &PolymerState) -> Result<u64, AocError> {
+    Ok(run_steps(state, 40))
+}
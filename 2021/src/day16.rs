@@ -1,171 +1,234 @@
-
-mod day16_parser;
-
-use day16_parser::hex2bin;
-use day16_parser::packet;
-use day16_parser::Packet;
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn count_version(p: &Packet) -> u32 {
-    match p {
-        Packet::Literal { metadata: m, .. } => m.version.into(),
-        Packet::Sum {
-            metadata: m,
-            packet: pp,
-        } => {
-            m.version as u32
-                + pp.subpackets
-                    .iter()
-                    .map(|sp| count_version(sp) as u32)
-                    .sum::<u32>()
-        },
-        Packet::Product {
-            metadata: m,
-            packet: pp,
-        } => {
-            m.version as u32
-                + pp.subpackets
-                    .iter()
-                    .map(|sp| count_version(sp) as u32)
-                    .sum::<u32>()
-        },
-        Packet::Minimum {
-            metadata: m,
-            packet: pp,
-        } => {
-            m.version as u32
-                + pp.subpackets
-                    .iter()
-                    .map(|sp| count_version(sp) as u32)
-                    .sum::<u32>()
-        },
-        Packet::Maximum {
-            metadata: m,
-            packet: pp,
-        } => {
-            m.version as u32
-                + pp.subpackets
-                    .iter()
-                    .map(|sp| count_version(sp) as u32)
-                    .sum::<u32>()
-        },
-        Packet::GreaterThan {
-            metadata: m,
-            packet: pp,
-        } => {
-            m.version as u32
-                + pp.subpackets
-                    .iter()
-                    .map(|sp| count_version(sp) as u32)
-                    .sum::<u32>()
-        },
-        Packet::LessThan {
-            metadata: m,
-            packet: pp,
-        } => {
-            m.version as u32
-                + pp.subpackets
-                    .iter()
-                    .map(|sp| count_version(sp) as u32)
-                    .sum::<u32>()
-        },
-        Packet::EqualTo {
-            metadata: m,
-            packet: pp,
-        } => {
-            m.version as u32
-                + pp.subpackets
-                    .iter()
-                    .map(|sp| count_version(sp) as u32)
-                    .sum::<u32>()
-        },
-    }
-}
-
-fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let line = input.next().unwrap()?;
-    let line = hex2bin(&line)?;
-
-    let (_, parsed) = packet(&line).unwrap();
-
-    Ok(count_version(&parsed))
-}
-
-fn eval(packet: &Packet) -> u64 {
-    match packet {
-        Packet::Literal { packet: p, .. } => {
-            p.value
-        },
-        Packet::Sum { packet: p, ..} => {
-            p.subpackets.iter().map(eval).sum()
-        },
-        Packet::Product { packet: p, ..} => {
-            p.subpackets.iter().fold(1, |acc, i| acc * eval(i))
-        },
-        Packet::Minimum { packet: p, ..} => {
-            p.subpackets.iter().map(eval).min().unwrap()
-        },
-        Packet::Maximum { packet: p, ..} => {
-            p.subpackets.iter().map(eval).max().unwrap()
-        },
-        Packet::GreaterThan { packet: p, ..} => {
-            if eval(&p.subpackets[0]) > eval(&p.subpackets[1]) {
-                1
-            } else {
-                0
-            }
-        },
-        Packet::LessThan { packet: p, ..} => {
-            if eval(&p.subpackets[0]) < eval(&p.subpackets[1]) {
-                1
-            } else {
-                0
-            }
-        },
-        Packet::EqualTo { packet: p, ..} => {
-            if eval(&p.subpackets[0]) == eval(&p.subpackets[1]) {
-                1
-            } else {
-                0
-            }
-        },
-    }
-}
-
-fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<u64, Box<dyn Error>> {
-    let line = input.next().unwrap()?;
-    let line = hex2bin(&line)?;
-
-    let (_, parsed) = packet(&line).unwrap();
-
-    Ok(eval(&parsed))
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2021!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day16")?;
-    println!("Part 1: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day16")?;
-    println!("Part 2: {}", part2(lines)?);
-
-    Ok(())
-}
+use crate::day16_parser::hex2bin;
+use crate::day16_parser::literal_bits;
+use crate::day16_parser::packet;
+use crate::day16_parser::Packet;
+use crate::day16_parser::PacketMetadata;
+use aoc_common::dot::Dot;
+use aoc_common::error::AocError;
+use aoc_common::random::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Packet;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        let line = input.lines().next().ok_or("empty input")?;
+        let bits = hex2bin(line)
+            .map_err(|e| AocError::at(input, line, format!("invalid hex digit: {}", e)))?;
+
+        let (_, parsed) = packet(&bits).map_err(|e| match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                // Point at the hex digit holding the bit where parsing broke.
+                let bit = bits.len() - e.input.len();
+                AocError::at(input, &line[bit / 4..], "malformed packet")
+            }
+            nom::Err::Incomplete(_) => AocError::at(input, &line[line.len()..], "truncated packet"),
+        })?;
+
+        Ok(parsed)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(count_version(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(eval(input).into())
+    }
+
+    fn dot(input: &Self::Input) -> Result<Dot, AocError> {
+        let mut dot = Dot::digraph();
+        add_to_dot(&mut dot, input, &mut 0);
+        Ok(dot)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, AocError> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn count_version(p: &Packet) -> u32 {
+    match p {
+        Packet::Literal { metadata: m, .. } => m.version.into(),
+        Packet::Sum {
+            metadata: m,
+            packet: pp,
+        } => m.version as u32 + pp.subpackets.iter().map(count_version).sum::<u32>(),
+        Packet::Product {
+            metadata: m,
+            packet: pp,
+        } => m.version as u32 + pp.subpackets.iter().map(count_version).sum::<u32>(),
+        Packet::Minimum {
+            metadata: m,
+            packet: pp,
+        } => m.version as u32 + pp.subpackets.iter().map(count_version).sum::<u32>(),
+        Packet::Maximum {
+            metadata: m,
+            packet: pp,
+        } => m.version as u32 + pp.subpackets.iter().map(count_version).sum::<u32>(),
+        Packet::GreaterThan {
+            metadata: m,
+            packet: pp,
+        } => m.version as u32 + pp.subpackets.iter().map(count_version).sum::<u32>(),
+        Packet::LessThan {
+            metadata: m,
+            packet: pp,
+        } => m.version as u32 + pp.subpackets.iter().map(count_version).sum::<u32>(),
+        Packet::EqualTo {
+            metadata: m,
+            packet: pp,
+        } => m.version as u32 + pp.subpackets.iter().map(count_version).sum::<u32>(),
+    }
+}
+
+fn eval(packet: &Packet) -> u64 {
+    match packet {
+        Packet::Literal { packet: p, .. } => p.value,
+        Packet::Sum { packet: p, .. } => p.subpackets.iter().map(eval).sum(),
+        Packet::Product { packet: p, .. } => p.subpackets.iter().fold(1, |acc, i| acc * eval(i)),
+        Packet::Minimum { packet: p, .. } => p.subpackets.iter().map(eval).min().unwrap(),
+        Packet::Maximum { packet: p, .. } => p.subpackets.iter().map(eval).max().unwrap(),
+        Packet::GreaterThan { packet: p, .. } => {
+            if eval(&p.subpackets[0]) > eval(&p.subpackets[1]) {
+                1
+            } else {
+                0
+            }
+        }
+        Packet::LessThan { packet: p, .. } => {
+            if eval(&p.subpackets[0]) < eval(&p.subpackets[1]) {
+                1
+            } else {
+                0
+            }
+        }
+        Packet::EqualTo { packet: p, .. } => {
+            if eval(&p.subpackets[0]) == eval(&p.subpackets[1]) {
+                1
+            } else {
+                0
+            }
+        }
+    }
+}
+
+// What `packet` does, its metadata and its subpackets.
+fn describe(packet: &Packet) -> (&'static str, &PacketMetadata, &[Packet]) {
+    match packet {
+        Packet::Literal { metadata, .. } => ("literal", metadata, &[]),
+        Packet::Sum { metadata, packet } => ("sum", metadata, &packet.subpackets),
+        Packet::Product { metadata, packet } => ("product", metadata, &packet.subpackets),
+        Packet::Minimum { metadata, packet } => ("minimum", metadata, &packet.subpackets),
+        Packet::Maximum { metadata, packet } => ("maximum", metadata, &packet.subpackets),
+        Packet::GreaterThan { metadata, packet } => ("greater than", metadata, &packet.subpackets),
+        Packet::LessThan { metadata, packet } => ("less than", metadata, &packet.subpackets),
+        Packet::EqualTo { metadata, packet } => ("equal to", metadata, &packet.subpackets),
+    }
+}
+
+// Adds `packet` and its subpackets, numbering them in the order they appear in the
+// transmission. Returns the id of `packet`.
+fn add_to_dot(dot: &mut Dot, packet: &Packet, next_id: &mut usize) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+
+    let (kind, metadata, subpackets) = describe(packet);
+    let label = format!(
+        "{}\nversion {}, type {}\n= {}",
+        kind,
+        metadata.version,
+        metadata.type_id,
+        eval(packet)
+    );
+    let shape = if subpackets.is_empty() {
+        "box"
+    } else {
+        "ellipse"
+    };
+    dot.node(id, &[("label", &label), ("shape", shape)]);
+
+    for subpacket in subpackets {
+        let child = add_to_dot(dot, subpacket, next_id);
+        dot.edge(id, child, &[]);
+    }
+
+    id
+}
+
+// A random packet of up to `*budget` packets in total, as bits, along with its value. Operators
+// whose value would overflow become minimums, so evaluating the transmission always works.
+fn generate_packet(rng: &mut Rng, budget: &mut usize, depth: usize) -> (String, u64) {
+    *budget -= 1;
+    let version = rng.range(0..=7);
+
+    let mut type_id = *rng.choose(&[0, 1, 2, 3, 5, 6, 7]);
+    let count = match type_id {
+        5..=7 => 2,
+        _ => rng.range(1..=4) as usize,
+    };
+    if depth >= 8 || *budget < count || rng.chance(0.4) {
+        let value = if rng.chance(0.1) {
+            rng.range(0..=u64::from(u32::MAX))
+        } else {
+            rng.range(0..=20)
+        };
+        return (literal_bits(version, value), value);
+    }
+
+    let mut children = Vec::new();
+    for i in 0..count {
+        // Leave enough packets for the remaining children.
+        let reserved = count - i - 1;
+        *budget -= reserved;
+        children.push(generate_packet(rng, budget, depth + 1));
+        *budget += reserved;
+    }
+    let values: Vec<u64> = children.iter().map(|(_, value)| *value).collect();
+
+    let value = match type_id {
+        0 => values.iter().try_fold(0u64, |acc, &v| acc.checked_add(v)),
+        1 => values.iter().try_fold(1u64, |acc, &v| acc.checked_mul(v)),
+        2 => values.iter().min().copied(),
+        3 => values.iter().max().copied(),
+        5 => Some(u64::from(values[0] > values[1])),
+        6 => Some(u64::from(values[0] < values[1])),
+        _ => Some(u64::from(values[0] == values[1])),
+    };
+    let value = value.unwrap_or_else(|| {
+        type_id = 2;
+        *values.iter().min().unwrap()
+    });
+
+    let subpackets: String = children.into_iter().map(|(bits, _)| bits).collect();
+    let length = if subpackets.len() < 1 << 15 && rng.chance(0.5) {
+        format!("0{:015b}", subpackets.len())
+    } else {
+        format!("1{:011b}", count)
+    };
+
+    (
+        format!("{:03b}{:03b}{}{}", version, type_id, length, subpackets),
+        value,
+    )
+}
+
+// A transmission of up to `size` packets, padded with zeros to whole bytes.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let (mut bits, _) = generate_packet(rng, &mut size.max(1), 0);
+    while bits.len() % 8 != 0 {
+        bits.push('0');
+    }
+
+    let mut hex: String = bits
+        .as_bytes()
+        .chunks(4)
+        .map(|nibble| {
+            let nibble = std::str::from_utf8(nibble).unwrap();
+            format!("{:X}", u8::from_str_radix(nibble, 2).unwrap())
+        })
+        .collect();
+    hex.push('\n');
+    hex
+}
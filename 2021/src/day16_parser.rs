@@ -1,10 +1,10 @@
 use nom::bytes::complete::tag;
+use nom::bytes::complete::take;
 use nom::combinator::{consumed, map_res};
-use nom::error::make_error;
+use nom::error::{make_error, ErrorKind};
 use nom::multi::{fold_many0, many_m_n};
 use nom::sequence::preceded;
 use nom::IResult;
-use nom::bytes::complete::take;
 use std::num::ParseIntError;
 
 #[derive(Debug, PartialEq)]
@@ -93,7 +93,7 @@ pub struct EqualToPacket {
 pub fn hex2bin(s: &str) -> Result<String, ParseIntError> {
     s.chars()
         .map(|c| u8::from_str_radix(&c.to_string(), 16))
-        .map(|u| u.and_then(|u| Ok(format!("{:04b}", u))))
+        .map(|u| u.map(|u| format!("{:04b}", u)))
         .collect()
 }
 
@@ -147,141 +147,122 @@ pub fn literal_packet(input: &str) -> IResult<&str, LiteralPacket> {
     ))
 }
 
+// The error for a packet that is well-formed bit by bit but makes no sense as a whole.
+fn malformed(input: &str) -> nom::Err<nom::error::Error<&str>> {
+    nom::Err::Error(make_error(input, ErrorKind::Fail))
+}
+
 pub fn subpackets(input: &str) -> IResult<&str, Vec<Packet>> {
-    let (input, length_type_id) = take(1usize)(input)?;
+    let (rest, length_type_id) = take(1usize)(input)?;
 
     match length_type_id {
         "0" => {
-            let (mut input, length) = take(15usize)(input)?;
-            let mut length = bin2dec(length).unwrap();
+            let (mut input, mut length) = map_res(take(15usize), bin2dec)(rest)?;
 
             let mut retval = Vec::new();
 
             while length > 0 {
-                let c = consumed(packet)(input)?;
-                input = c.0;
-                let (consumed, subpacket) = c.1;
+                let (rest, (consumed, subpacket)) = consumed(packet)(input)?;
+                // Subpackets running past the announced length.
+                length = length
+                    .checked_sub(consumed.len() as u32)
+                    .ok_or_else(|| malformed(input))?;
+                input = rest;
                 retval.push(subpacket);
-                length -= consumed.len() as u32;
             }
-            assert!(length == 0, "length inconsistent");
 
             Ok((input, retval))
         }
         "1" => {
-            let (input, num_subpackets) = take(11usize)(input)?;
-            let num_subpackets = bin2dec(num_subpackets).unwrap();
+            let (input, num_subpackets) = map_res(take(11usize), bin2dec)(rest)?;
 
             let (input, retval) =
                 many_m_n(num_subpackets as usize, num_subpackets as usize, packet)(input)?;
 
             Ok((input, retval))
         }
-        _ => {
-            panic!("unexpected bit value")
-        }
+        _ => Err(malformed(input)),
     }
 }
 
-pub fn packet(input: &str) -> IResult<&str, Packet> {
-    let (input, version) = map_res(take(3usize), parse_version)(input)?;
-    let (input, type_id) = map_res(take(3usize), parse_type_id)(input)?;
-
-    let metadata = PacketMetadata { version, type_id };
-
-    match type_id {
-        4 => {
-            let (input, literal_packet) = literal_packet(input)?;
-            return Ok((
-                input,
-                Packet::Literal {
-                    metadata: metadata,
-                    packet: literal_packet,
-                },
-            ));
-        },
-        0 => {
-            let (input, sp) = subpackets(input)?;
-            return Ok((
-                input,
-                Packet::Sum {
-                    metadata: metadata,
-                    packet: SumPacket { subpackets: sp },
-                },
-            ));
+// An operator packet, unless it has the wrong number of subpackets: comparisons compare exactly
+// two, the others need at least one.
+fn operator(metadata: PacketMetadata, subpackets: Vec<Packet>) -> Option<Packet> {
+    let packet = match (metadata.type_id, subpackets.len()) {
+        (_, 0) => return None,
+        (0, _) => Packet::Sum {
+            metadata,
+            packet: SumPacket { subpackets },
         },
-        1 => {
-            let (input, sp) = subpackets(input)?;
-            return Ok((
-                input,
-                Packet::Product {
-                    metadata: metadata,
-                    packet: ProductPacket { subpackets: sp },
-                },
-            ));
+        (1, _) => Packet::Product {
+            metadata,
+            packet: ProductPacket { subpackets },
         },
-        2 => {
-            let (input, sp) = subpackets(input)?;
-            return Ok((
-                input,
-                Packet::Minimum {
-                    metadata: metadata,
-                    packet: MinimumPacket { subpackets: sp },
-                },
-            ));
+        (2, _) => Packet::Minimum {
+            metadata,
+            packet: MinimumPacket { subpackets },
         },
-        3 => {
-            let (input, sp) = subpackets(input)?;
-            return Ok((
-                input,
-                Packet::Maximum {
-                    metadata: metadata,
-                    packet: MaximumPacket { subpackets: sp },
-                },
-            ));
+        (3, _) => Packet::Maximum {
+            metadata,
+            packet: MaximumPacket { subpackets },
         },
-        5 => {
-            let (input, sp) = subpackets(input)?;
-            return Ok((
-                input,
-                Packet::GreaterThan {
-                    metadata: metadata,
-                    packet: GreaterThanPacket { subpackets: sp },
-                },
-            ));
+        (5, 2) => Packet::GreaterThan {
+            metadata,
+            packet: GreaterThanPacket { subpackets },
         },
-        6 => {
-            let (input, sp) = subpackets(input)?;
-            return Ok((
-                input,
-                Packet::LessThan {
-                    metadata: metadata,
-                    packet: LessThanPacket { subpackets: sp },
-                },
-            ));
+        (6, 2) => Packet::LessThan {
+            metadata,
+            packet: LessThanPacket { subpackets },
         },
-        7 => {
-            let (input, sp) = subpackets(input)?;
-            return Ok((
-                input,
-                Packet::EqualTo {
-                    metadata: metadata,
-                    packet: EqualToPacket { subpackets: sp },
-                },
-            ));
+        (7, 2) => Packet::EqualTo {
+            metadata,
+            packet: EqualToPacket { subpackets },
         },
-        _ => {
-            // Not sure what to do about this. I just want an error.
-            Err(nom::Err::Error(make_error(
-                input,
-                nom::error::ErrorKind::Fail,
-            )))
-        }
+        _ => return None,
+    };
+
+    Some(packet)
+}
+
+pub fn packet(input: &str) -> IResult<&str, Packet> {
+    let (input, version) = map_res(take(3usize), parse_version)(input)?;
+    let (input, type_id) = map_res(take(3usize), parse_type_id)(input)?;
+
+    let metadata = PacketMetadata { version, type_id };
+
+    if type_id == 4 {
+        let (input, literal_packet) = literal_packet(input)?;
+        return Ok((
+            input,
+            Packet::Literal {
+                metadata,
+                packet: literal_packet,
+            },
+        ));
+    }
+
+    let (rest, sp) = subpackets(input)?;
+    let packet = operator(metadata, sp).ok_or_else(|| malformed(input))?;
+
+    Ok((rest, packet))
+}
+
+/// A literal packet as bits, the value split into groups of four bits.
+pub fn literal_bits(version: u64, value: u64) -> String {
+    let mut bits = format!("{:03b}100", version);
+
+    // Groups of four bits, the most significant first. Each but the last is prefixed with a 1.
+    let groups = (64 - value.leading_zeros()).div_ceil(4).max(1);
+    for group in (0..groups).rev() {
+        let prefix = if group == 0 { '0' } else { '1' };
+        bits.push_str(&format!("{}{:04b}", prefix, (value >> (group * 4)) & 0xf));
     }
+
+    bits
 }
 
 #[test]
-fn test_literal() -> Result<(), Box<dyn Error>> {
+fn test_literal() -> Result<(), Box<dyn std::error::Error>> {
     let p = hex2bin("D2FE28")?;
     let (r, p) = packet(&p).unwrap();
 
@@ -296,7 +277,7 @@ fn test_literal() -> Result<(), Box<dyn Error>> {
 }
 
 #[test]
-fn test_operator_len_type_0() -> Result<(), Box<dyn Error>> {
+fn test_operator_len_type_0() -> Result<(), Box<dyn std::error::Error>> {
     let p = hex2bin("38006F45291200")?;
     let (r, p) = packet(&p).unwrap();
 
@@ -317,7 +298,7 @@ fn test_operator_len_type_0() -> Result<(), Box<dyn Error>> {
 }
 
 #[test]
-fn test_operator_len_type_1() -> Result<(), Box<dyn Error>> {
+fn test_operator_len_type_1() -> Result<(), Box<dyn std::error::Error>> {
     let p = hex2bin("EE00D40C823060")?;
     let (r, p) = packet(&p).unwrap();
 
@@ -339,3 +320,88 @@ fn test_operator_len_type_1() -> Result<(), Box<dyn Error>> {
         _ => Err("wrong packet type".into()),
     }
 }
+
+#[test]
+fn test_malformed() {
+    let literal = literal_bits(1, 5);
+    assert_eq!(literal.len(), 11);
+
+    // Comparing a single subpacket.
+    assert!(packet(&format!("0001101{:011b}{}", 1, literal)).is_err());
+    // Subpackets running past the 10 bits they were supposed to take.
+    assert!(packet(&format!("0000000{:015b}{}", 10, literal)).is_err());
+    assert!(packet(&format!("000000x{}", literal)).is_err());
+    assert!(packet(&format!("0000001{:011b}{}", 0, literal)).is_err());
+    assert!(packet("0000").is_err());
+}
+
+// `packet` as bits, counting its subpackets or giving their length.
+#[cfg(test)]
+fn to_bits(packet: &Packet, by_count: bool) -> String {
+    let (metadata, subpackets) = match packet {
+        Packet::Literal { metadata, packet } => {
+            return literal_bits(metadata.version.into(), packet.value)
+        }
+        Packet::Sum { metadata, packet } => (metadata, &packet.subpackets),
+        Packet::Product { metadata, packet } => (metadata, &packet.subpackets),
+        Packet::Minimum { metadata, packet } => (metadata, &packet.subpackets),
+        Packet::Maximum { metadata, packet } => (metadata, &packet.subpackets),
+        Packet::GreaterThan { metadata, packet } => (metadata, &packet.subpackets),
+        Packet::LessThan { metadata, packet } => (metadata, &packet.subpackets),
+        Packet::EqualTo { metadata, packet } => (metadata, &packet.subpackets),
+    };
+
+    let bits: String = subpackets.iter().map(|p| to_bits(p, by_count)).collect();
+    let length = if by_count {
+        format!("1{:011b}", subpackets.len())
+    } else {
+        format!("0{:015b}", bits.len())
+    };
+
+    format!(
+        "{:03b}{:03b}{}{}",
+        metadata.version, metadata.type_id, length, bits
+    )
+}
+
+#[cfg(test)]
+fn packets() -> impl proptest::strategy::Strategy<Value = Packet> {
+    use proptest::prelude::*;
+
+    let literal = (0..8u8, any::<u64>()).prop_map(|(version, value)| Packet::Literal {
+        metadata: PacketMetadata {
+            version,
+            type_id: 4,
+        },
+        packet: LiteralPacket { value },
+    });
+
+    literal.prop_recursive(4, 64, 4, |inner| {
+        prop_oneof![
+            (0..8u8, 0..4u8, prop::collection::vec(inner.clone(), 1..=4)),
+            (0..8u8, 5..8u8, prop::collection::vec(inner, 2)),
+        ]
+        .prop_map(|(version, type_id, subpackets)| {
+            operator(PacketMetadata { version, type_id }, subpackets).unwrap()
+        })
+    })
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_packet_roundtrip(packet in packets(), by_count: bool) {
+        let bits = to_bits(&packet, by_count);
+        proptest::prop_assert_eq!(self::packet(&bits), Ok(("", packet)));
+    }
+
+    #[test]
+    fn test_packet_bits(bits in "[01]{0,300}") {
+        let _ = packet(&bits);
+    }
+
+    #[test]
+    fn test_packet_anything(input: String) {
+        let _ = packet(&input);
+    }
+}
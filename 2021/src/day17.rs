@@ -1,19 +1,48 @@
-use regex::Regex;
-use std::cell::RefCell;
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::hash::{Hash, Hasher};
-use std::io::{self, BufRead};
-use std::path::Path;
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+use aoc_common::error::AocError;
+use aoc_common::parsers::{key_value, parse_all, range, signed};
+use aoc_common::solution::{Answer, Solution};
+use log::{debug, trace};
+use nom::bytes::complete::tag;
+use nom::sequence::{preceded, separated_pair};
+use std::collections::HashSet;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Target;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
+}
+
+// x0, x1, y0, y1
+type Target = (i32, i32, i32, i32);
+
+fn parse_input(input: &str) -> Result<Target, AocError> {
+    let ((x0, x1), (y0, y1)) = parse_all(
+        input,
+        preceded(
+            tag("target area: "),
+            separated_pair(
+                key_value("x", range("..", signed)),
+                tag(", "),
+                key_value("y", range("..", signed)),
+            ),
+        ),
+    )?;
+
+    debug!("{}, {}, {}, {}", x0, x1, y0, y1);
+
+    Ok((x0, x1, y0, y1))
 }
 
 // fn steps_to_zone(x: u32, x0: u32, x1: u32) -> Vec<u32> {
@@ -38,39 +67,25 @@ where
 //     vec![1, 2, 3]
 // }
 
-fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<(i32, i32), Box<dyn Error>> {
-    let line = input.next().unwrap()?;
-    let re = Regex::new(r"^target area: x=(-?\d+)..(-?\d+), y=(-?\d+)..(-?\d+)")?;
-    let captures = re.captures(&line).unwrap();
-
-    let x0 = captures.get(1).unwrap().as_str().parse::<i32>().unwrap();
-    let x1 = captures.get(2).unwrap().as_str().parse::<i32>().unwrap();
-    let y0 = captures.get(3).unwrap().as_str().parse::<i32>().unwrap();
-    let y1 = captures.get(4).unwrap().as_str().parse::<i32>().unwrap();
-
-    println!("{}, {}, {}, {}", x0, x1, y0, y1);
-
+fn part1(&(x0, x1, y0, y1): &Target) -> Result<i32, AocError> {
     // Just brute-force. Don't assume anything about how the scale or distribution of the input coordinates.
 
     if x0 < 0 && x1 > 0 {
         panic!("unsupported")
     }
 
-    let mut y;
-
-    if y1 <= 0 {
+    let y = if y1 <= 0 {
         // Very extreme lower bound, but gives us a good starting point for brute force.
         // (x0, y) always hits the target area in 1 step, thus y is a solution.
-        y = y0 - 1;
+        y0 - 1
     } else {
-        y = 0;
-    }
+        0
+    };
 
     let mut highest_y = i32::MIN;
-    let mut best_solution = (0, i32::MIN);
 
     for y in y..=x1 {
-        println!("Trying y = {}", y);
+        trace!("Trying y = {}", y);
 
         // First determine if this y can hit the target area in any amount of steps.
         let mut y_pos = 0;
@@ -93,14 +108,9 @@ fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<(i32, i32), Box<dy
             }
 
             if y_pos >= y0 && y_pos <= y1 {
-                println!("y {} hits in {} steps", y, steps);
+                trace!("y {} hits in {} steps", y, steps);
                 // Check if a corresponding x exists that can hit the target area in the same amount of steps.
-                let r;
-                if x0 >= 0 {
-                    r = 0..=x1;
-                } else {
-                    r = x1..=0;
-                }
+                let r = if x0 >= 0 { 0..=x1 } else { x1..=0 };
 
                 for x in r {
                     let mut x_pos = 0;
@@ -120,13 +130,12 @@ fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<(i32, i32), Box<dy
                     // println!("Trying ({}, {}) = {}, {}", x, y, x_pos, y_pos);
 
                     if x_pos >= x0 && x_pos <= x1 {
-                        println!(
+                        debug!(
                             "Solution found: {},{}. Highest y_pos: {}",
                             x, y, highest_pos_for_this_y
                         );
                         if highest_pos_for_this_y > highest_y {
                             highest_y = highest_pos_for_this_y;
-                            best_solution = (x, y);
                         }
                     }
                 }
@@ -134,36 +143,23 @@ fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<(i32, i32), Box<dy
         }
     }
 
-    Ok(best_solution)
+    Ok(highest_y)
 }
 
-fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<usize, Box<dyn Error>> {
-    let line = input.next().unwrap()?;
-    let re = Regex::new(r"^target area: x=(-?\d+)..(-?\d+), y=(-?\d+)..(-?\d+)")?;
-    let captures = re.captures(&line).unwrap();
-
-    let x0 = captures.get(1).unwrap().as_str().parse::<i32>().unwrap();
-    let x1 = captures.get(2).unwrap().as_str().parse::<i32>().unwrap();
-    let y0 = captures.get(3).unwrap().as_str().parse::<i32>().unwrap();
-    let y1 = captures.get(4).unwrap().as_str().parse::<i32>().unwrap();
-
-    println!("{}, {}, {}, {}", x0, x1, y0, y1);
-
+fn part2(&(x0, x1, y0, y1): &Target) -> Result<usize, AocError> {
     // Just brute-force. Don't assume anything about how the scale or distribution of the input coordinates.
 
     if x0 < 0 && x1 > 0 {
         panic!("unsupported")
     }
 
-    let mut y;
-
-    if y1 <= 0 {
+    let y = if y1 <= 0 {
         // Very extreme lower bound, but gives us a good starting point for brute force.
         // (x0, y) always hits the target area in 1 step, thus y is a solution.
-        y = y0 - 1;
+        y0 - 1
     } else {
-        y = 0;
-    }
+        0
+    };
 
     let mut solutions = HashSet::new();
 
@@ -191,14 +187,9 @@ fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<usize, Box<dyn Err
             }
 
             if y_pos >= y0 && y_pos <= y1 {
-                println!("y {} hits in {} steps", y, steps);
+                trace!("y {} hits in {} steps", y, steps);
                 // Check if a corresponding x exists that can hit the target area in the same amount of steps.
-                let r;
-                if x0 >= 0 {
-                    r = 0..=x1;
-                } else {
-                    r = x1..=0;
-                }
+                let r = if x0 >= 0 { 0..=x1 } else { x1..=0 };
 
                 for x in r {
                     let mut x_pos = 0;
@@ -217,7 +208,7 @@ fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<usize, Box<dyn Err
                     // println!("Trying ({}, {}) = {}, {}", x, y, x_pos, y_pos);
 
                     if x_pos >= x0 && x_pos <= x1 {
-                        println!(
+                        debug!(
                             "Solution found: {},{}. Highest y_pos: {}",
                             x, y, highest_pos_for_this_y
                         );
@@ -230,21 +221,3 @@ fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<usize, Box<dyn Err
 
     Ok(solutions.len())
 }
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2021!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day17")?;
-    println!("Part 1: {:?}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day17")?;
-    println!("Part 2: {}", part2(lines)?);
-
-    Ok(())
-}
-
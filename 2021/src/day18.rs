@@ -1,84 +1,225 @@
-mod day18_parser_unsafe;
-
-use day18_parser_unsafe::SnailfishNumber;
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    // let (_, mut acc) = SnailfishNumber::from(&input.next().unwrap()?).unwrap();
-    // println!("{:?}", acc);
-
-    // for line in input {
-    //     let line = line?;
-
-    //     let (_, number) = SnailfishNumber::from(&line).unwrap();
-
-    //     acc = &acc + &number;
-
-    //     // println!("After add: {:?}", acc);
-    // }
-
-    // Ok(acc.magnitude())
-    Ok(0)
-}
-
-fn part2(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    // let mut numbers = Vec::new();
-
-    // for line in input {
-    //     let line = line?;
-    //     let (_, number) = SnailfishNumber::from(&line).unwrap();
-    //     numbers.push(number);
-    // }
-
-    // let mut largest_mag = 0;
-
-    // for i in 0..numbers.len() {
-    //     for j in 0..numbers.len() {
-    //         if i == j {
-    //             continue;
-    //         }
-
-    //         let mag = (&numbers[i] + &numbers[j]).magnitude();
-
-    //         if mag > largest_mag {
-    //             println!(
-    //                 "Better solution: {} using {:?} and {:?}",
-    //                 mag, numbers[i], numbers[j]
-    //             );
-    //             largest_mag = mag;
-    //         }
-    //     }
-    // }
-
-    // Ok(largest_mag)
-    Ok(0)
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2021!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day18")?;
-    println!("Part 1: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day18")?;
-    println!("Part 2: {}", part2(lines)?);
-
-    Ok(())
-}
+use aoc_common::error::AocError;
+use aoc_common::parsers::lines;
+#[cfg(test)]
+use aoc_common::parsers::parse_all;
+use aoc_common::solution::{Answer, Solution};
+use nom::branch::alt;
+use nom::character::complete::{anychar, char};
+use nom::combinator::map_opt;
+use nom::error::{make_error, ErrorKind};
+use nom::IResult;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Number>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        lines(input, snailfish_number)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
+}
+
+// Flattening a snailfish number into its regular numbers, each tagged with how deeply it is
+// nested, keeps the left and right neighbours needed for exploding right next to each other.
+type Number = Vec<(u32, u32)>;
+
+// Reduced numbers, which are all the puzzle hands out, nest regular numbers at most this deep.
+// Adding deeper ones would need more than one explosion per pair, which `explode` doesn't do.
+const MAX_DEPTH: u32 = 4;
+
+fn regular_number(input: &str, depth: u32) -> IResult<&str, Number> {
+    let (input, value) = map_opt(anychar, |c| c.to_digit(10))(input)?;
+    Ok((input, vec![(value, depth)]))
+}
+
+fn pair(input: &str, depth: u32) -> IResult<&str, Number> {
+    let (input, _) = char('[')(input)?;
+    if depth == MAX_DEPTH {
+        return Err(nom::Err::Failure(make_error(input, ErrorKind::TooLarge)));
+    }
+
+    let (input, mut left) = nested(input, depth + 1)?;
+    let (input, _) = char(',')(input)?;
+    let (input, right) = nested(input, depth + 1)?;
+    let (input, _) = char(']')(input)?;
+
+    left.extend(right);
+    Ok((input, left))
+}
+
+// A snailfish number nested `depth` deep, flattened.
+fn nested(input: &str, depth: u32) -> IResult<&str, Number> {
+    alt((|i| pair(i, depth), |i| regular_number(i, depth)))(input)
+}
+
+fn snailfish_number(input: &str) -> IResult<&str, Number> {
+    nested(input, 0)
+}
+
+fn explode(number: &mut Number) -> bool {
+    // Numbers are reduced after every addition, so nothing is nested deeper than a pair of
+    // regular numbers at depth 5.
+    let Some(i) = number.iter().position(|&(_, depth)| depth > 4) else {
+        return false;
+    };
+
+    let (left, depth) = number[i];
+    let (right, _) = number[i + 1];
+
+    if i > 0 {
+        number[i - 1].0 += left;
+    }
+    if i + 2 < number.len() {
+        number[i + 2].0 += right;
+    }
+
+    number.splice(i..i + 2, [(0, depth - 1)]);
+
+    true
+}
+
+fn split(number: &mut Number) -> bool {
+    let Some(i) = number.iter().position(|&(value, _)| value >= 10) else {
+        return false;
+    };
+
+    let (value, depth) = number[i];
+    number.splice(
+        i..i + 1,
+        [(value / 2, depth + 1), (value.div_ceil(2), depth + 1)],
+    );
+
+    true
+}
+
+fn add(left: &Number, right: &Number) -> Number {
+    let mut sum: Number = left
+        .iter()
+        .chain(right)
+        .map(|&(value, depth)| (value, depth + 1))
+        .collect();
+
+    while explode(&mut sum) || split(&mut sum) {}
+
+    sum
+}
+
+fn magnitude(number: &Number) -> u32 {
+    let mut number = number.clone();
+
+    // The leftmost of the most deeply nested numbers is always the left half of a pair.
+    while number.len() > 1 {
+        let max_depth = number.iter().map(|&(_, depth)| depth).max().unwrap();
+        let i = number
+            .iter()
+            .position(|&(_, depth)| depth == max_depth)
+            .unwrap();
+
+        let pair = 3 * number[i].0 + 2 * number[i + 1].0;
+        number.splice(i..i + 2, [(pair, max_depth - 1)]);
+    }
+
+    number[0].0
+}
+
+fn part1(input: &[Number]) -> Result<u32, AocError> {
+    let (first, rest) = input.split_first().ok_or("empty input")?;
+
+    let sum = rest
+        .iter()
+        .fold(first.clone(), |acc, number| add(&acc, number));
+
+    Ok(magnitude(&sum))
+}
+
+fn part2(input: &[Number]) -> Result<u32, AocError> {
+    let mut largest_mag = 0;
+
+    for i in 0..input.len() {
+        for j in 0..input.len() {
+            if i == j {
+                continue;
+            }
+
+            let mag = magnitude(&add(&input[i], &input[j]));
+
+            if mag > largest_mag {
+                largest_mag = mag;
+            }
+        }
+    }
+
+    Ok(largest_mag)
+}
+
+#[test]
+fn test_explode() {
+    // [[6,[5,[4,[3,2]]]],1], too deep to parse.
+    let mut number = vec![(6, 2), (5, 3), (4, 4), (3, 5), (2, 5), (1, 1)];
+    assert!(explode(&mut number));
+    assert_eq!(
+        number,
+        parse_all("[[6,[5,[7,0]]],3]", snailfish_number).unwrap()
+    );
+}
+
+#[test]
+fn test_magnitude() {
+    let number = parse_all(
+        "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+        snailfish_number,
+    )
+    .unwrap();
+    assert_eq!(magnitude(&number), 3488);
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        parse_all("[[1,2],3]", snailfish_number).unwrap(),
+        [(1, 2), (2, 2), (3, 1)]
+    );
+    assert!(parse_all("[1,2", snailfish_number).is_err());
+    assert!(parse_all("[10,2]", snailfish_number).is_err());
+    assert!(parse_all("[[[[1,2],3],4],5]", snailfish_number).is_ok());
+    assert!(parse_all("[[[[[1,2],3],4],5],6]", snailfish_number).is_err());
+    assert!(parse_all(&"[".repeat(100_000), snailfish_number).is_err());
+}
+
+// A random snailfish number along with its flattened form.
+#[cfg(test)]
+fn snailfish_numbers() -> impl proptest::strategy::Strategy<Value = (String, Number)> {
+    use proptest::prelude::*;
+
+    let regular = (0..10u32).prop_map(|value| (value.to_string(), vec![(value, 0)]));
+    regular.prop_recursive(MAX_DEPTH, 64, 2, |inner| {
+        (inner.clone(), inner).prop_map(|((left, mut numbers), (right, right_numbers))| {
+            numbers.extend(right_numbers);
+            for (_, depth) in &mut numbers {
+                *depth += 1;
+            }
+            (format!("[{},{}]", left, right), numbers)
+        })
+    })
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parse_roundtrip((text, number) in snailfish_numbers()) {
+        proptest::prop_assert_eq!(parse_all(&text, snailfish_number).unwrap(), number);
+    }
+
+    #[test]
+    fn test_parse_anything(input in "[\\[\\],0-9]{0,100}|.*") {
+        let _ = snailfish_number(&input);
+    }
+}
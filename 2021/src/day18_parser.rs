@@ -17,9 +17,11 @@ pub struct SnailfishNumber {
     data: Rc<Sn>,
 }
 
+// Numbers are equal if they look the same. Walking the tree needs to know whether two handles point
+// at the same node, see `is`.
 impl PartialEq for SnailfishNumber {
     fn eq(&self, other: &Self) -> bool {
-        Rc::ptr_eq(&self.data, &other.data)
+        self.data == other.data
     }
 }
 
@@ -43,6 +45,11 @@ impl SnailfishNumber {
         Ok((input, data))
     }
 
+    // Whether `self` and `other` are the same node, not just equal ones.
+    fn is(&self, other: &SnailfishNumber) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+
     pub fn len(&self) -> usize {
         self.data.len()
     }
@@ -120,31 +127,22 @@ impl SnailfishNumber {
     fn verify_parents_internal(&self, expected_parent: Option<&SnailfishNumber>) {
         match self.data.deref() {
             Sn::Pair {
-                left: l,
-                right: r,
-                parent: p,
+                left: l, right: r, ..
             } => {
                 l.borrow().verify_parents_internal(Some(self));
 
                 r.borrow().verify_parents_internal(Some(self));
             }
-            Sn::Number {
-                value: v,
-                parent: p,
-            } => {
-                if expected_parent.is_none() {
-                    if !p.borrow().upgrade().is_none() {
-                        panic!("parent not expected, but found");
-                    }
-                } else {
-                    if p.borrow().upgrade().is_none() {
-                        panic!("did not find a parent for {:?}", self);
-                    }
-                    if p.borrow().upgrade().unwrap() != expected_parent.unwrap().data {
+            Sn::Number { parent: p, .. } => match (expected_parent, p.borrow().upgrade()) {
+                (None, None) => {}
+                (None, Some(_)) => panic!("parent not expected, but found"),
+                (Some(_), None) => panic!("did not find a parent for {:?}", self),
+                (Some(expected), Some(parent)) => {
+                    if !Rc::ptr_eq(&parent, &expected.data) {
                         panic!("fail");
                     }
                 }
-            }
+            },
         }
     }
 }
@@ -212,10 +210,7 @@ fn get_left(n: &SnailfishNumber) -> Option<SnailfishNumber> {
     loop {
         // println!("iteration with node: {:?}", node);
         let parent = get_parent(&node);
-        if let None = parent {
-            return None;
-        }
-        let parent = parent.unwrap();
+        let parent = parent?;
 
         if let Sn::Pair {
             left: l, right: r, ..
@@ -223,7 +218,7 @@ fn get_left(n: &SnailfishNumber) -> Option<SnailfishNumber> {
         {
             let r = r.borrow();
             // println!("determining right child: {:?}", r);
-            if node == **r {
+            if node.is(&r) {
                 return Some(get_max(&l.borrow()));
             }
         }
@@ -240,17 +235,14 @@ fn get_right(n: &SnailfishNumber) -> Option<SnailfishNumber> {
 
     loop {
         let parent = get_parent(&node);
-        if let None = parent {
-            return None;
-        }
-        let parent = parent.unwrap();
+        let parent = parent?;
 
         if let Sn::Pair {
             left: l, right: r, ..
         } = parent.data.deref()
         {
             let l = l.borrow();
-            if node == **l {
+            if node.is(&l) {
                 return Some(get_min(&r.borrow()));
             }
         }
@@ -295,7 +287,7 @@ impl Add for &SnailfishNumber {
                         {
                             if let Sn::Number { value: v, .. } = left_neighbour.data.deref() {
                                 let mut v_borrow = v.borrow_mut();
-                                *v_borrow = *v_borrow + *explode_left.borrow();
+                                *v_borrow += *explode_left.borrow();
                             } else {
                                 panic!("Neighbour found, but was not a number");
                             }
@@ -311,7 +303,7 @@ impl Add for &SnailfishNumber {
                         {
                             if let Sn::Number { value: v, .. } = right_neighbour.data.deref() {
                                 let mut v_borrow = v.borrow_mut();
-                                *v_borrow = *v_borrow + *explode_right.borrow();
+                                *v_borrow += *explode_right.borrow();
                             } else {
                                 panic!("Neighbour found, but was not a number");
                             }
@@ -345,25 +337,20 @@ impl Add for &SnailfishNumber {
 }
 
 fn replace_with_number(node: SnailfishNumber, new_value: u32) {
-    let parent = get_parent(&node);
-
-    if let None = parent {
-        panic!("unsupported");
-    }
-    let parent = parent.unwrap();
+    let parent = get_parent(&node).expect("unsupported");
 
     if let Sn::Pair {
         left: l, right: r, ..
     } = parent.data.deref()
     {
-        if node == **l.borrow() {
+        if node.is(&l.borrow()) {
             *l.borrow_mut() = Rc::new(SnailfishNumber {
                 data: Rc::new(Sn::Number {
                     value: RefCell::new(new_value),
                     parent: RefCell::new(Weak::new()),
                 }),
             });
-        } else if node == **r.borrow() {
+        } else if node.is(&r.borrow()) {
             *r.borrow_mut() = Rc::new(SnailfishNumber {
                 data: Rc::new(Sn::Number {
                     value: RefCell::new(new_value),
@@ -379,13 +366,9 @@ fn replace_with_number(node: SnailfishNumber, new_value: u32) {
 }
 
 fn replace_with_pair(node: SnailfishNumber) {
-    let parent = get_parent(&node);
-
-    if let None = parent {
-        println!("{:?}", node);
-        panic!("Number to split did not have a parent");
-    }
-    let parent = parent.unwrap();
+    let Some(parent) = get_parent(&node) else {
+        panic!("Number to split did not have a parent: {:?}", node);
+    };
 
     let old_node_value;
     if let Sn::Number { value: v, .. } = node.data.deref() {
@@ -404,7 +387,7 @@ fn replace_with_pair(node: SnailfishNumber) {
             })),
             right: RefCell::new(Rc::new(SnailfishNumber {
                 data: Rc::new(Sn::Number {
-                    value: RefCell::new((old_node_value + 1) / 2),
+                    value: RefCell::new(old_node_value.div_ceil(2)),
                     parent: RefCell::new(Weak::new()),
                 }),
             })),
@@ -417,9 +400,9 @@ fn replace_with_pair(node: SnailfishNumber) {
         left: l, right: r, ..
     } = parent.data.deref()
     {
-        if node == **l.borrow() {
+        if node.is(&l.borrow()) {
             *l.borrow_mut() = new_pair;
-        } else if node == **r.borrow() {
+        } else if node.is(&r.borrow()) {
             *r.borrow_mut() = new_pair;
         } else {
             panic!("could not determine if node is left or right child");
@@ -478,15 +461,7 @@ impl Sn {
     }
 }
 
-fn sfn_pair(input: &str) -> IResult<&str, SnailfishNumber> {
-    let (input, (_, left, _, right, _)) = tuple((
-        tag("["),
-        snailfish_number,
-        tag(","),
-        snailfish_number,
-        tag("]"),
-    ))(input)?;
-
+fn new_pair(left: SnailfishNumber, right: SnailfishNumber) -> SnailfishNumber {
     let retval = SnailfishNumber {
         data: Rc::new(Sn::Pair {
             left: RefCell::new(Rc::new(left)),
@@ -497,7 +472,19 @@ fn sfn_pair(input: &str) -> IResult<&str, SnailfishNumber> {
 
     retval.set_children_parent_pointers();
 
-    Ok((input, retval))
+    retval
+}
+
+fn sfn_pair(input: &str) -> IResult<&str, SnailfishNumber> {
+    let (input, (_, left, _, right, _)) = tuple((
+        tag("["),
+        snailfish_number,
+        tag(","),
+        snailfish_number,
+        tag("]"),
+    ))(input)?;
+
+    Ok((input, new_pair(left, right)))
 }
 
 fn sfn_number(input: &str) -> IResult<&str, SnailfishNumber> {
@@ -680,3 +667,32 @@ fn test_add() {
     let (_, expected) = SnailfishNumber::from("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").unwrap();
     assert_eq!(&n1 + &n2, expected);
 }
+
+#[cfg(test)]
+fn snailfish_numbers() -> impl proptest::strategy::Strategy<Value = SnailfishNumber> {
+    use proptest::prelude::*;
+
+    let number = (0..100u32).prop_map(|value| SnailfishNumber {
+        data: Rc::new(Sn::Number {
+            value: RefCell::new(value),
+            parent: RefCell::new(Weak::new()),
+        }),
+    });
+    number.prop_recursive(6, 64, 2, |inner| {
+        (inner.clone(), inner).prop_map(|(left, right)| new_pair(left, right))
+    })
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    // `Debug` writes numbers the way the puzzle does.
+    #[test]
+    fn test_parse_roundtrip(number in snailfish_numbers()) {
+        let text = format!("{:?}", number);
+        let (rest, parsed) = snailfish_number(&text).unwrap();
+
+        proptest::prop_assert_eq!(rest, "");
+        parsed.verify_parents();
+        proptest::prop_assert_eq!(parsed, number);
+    }
+}
@@ -0,0 +1,51 @@
+use aoc_common::solution::{Puzzle, Year};
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day16_parser;
+mod day17;
+mod day18;
+// A first, tree based attempt at day 18. Only its own tests use it.
+#[allow(dead_code)]
+mod day18_parser;
+mod day25;
+
+pub const YEAR: Year = Year {
+    year: 2021,
+    input_dir: concat!(env!("CARGO_MANIFEST_DIR"), "/src/input"),
+    puzzles: &[
+        Puzzle::new::<day01::Day01>(1),
+        Puzzle::new::<day02::Day02>(2),
+        Puzzle::new::<day03::Day03>(3),
+        Puzzle::new::<day04::Day04>(4),
+        Puzzle::new::<day05::Day05>(5),
+        Puzzle::new::<day06::Day06>(6),
+        Puzzle::new::<day07::Day07>(7),
+        Puzzle::new::<day08::Day08>(8),
+        Puzzle::new::<day09::Day09>(9),
+        Puzzle::new::<day10::Day10>(10),
+        Puzzle::new::<day11::Day11>(11),
+        Puzzle::new::<day12::Day12>(12),
+        Puzzle::new::<day13::Day13>(13),
+        Puzzle::new::<day14::Day14>(14),
+        Puzzle::new::<day15::Day15>(15),
+        Puzzle::new::<day16::Day16>(16),
+        Puzzle::new::<day17::Day17>(17),
+        Puzzle::new::<day18::Day18>(18),
+        Puzzle::new::<day25::Day25>(25),
+    ],
+};
@@ -0,0 +1,7 @@
+use std::env;
+use std::path::Path;
+
+fn main() {
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    aoc_common::examples::generate_tests(Path::new("src/input"), &out);
+}
@@ -1,43 +1,43 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::error::Error;
-use std::env;
-use std::collections::BinaryHeap;
+use aoc_common::error::AocError;
+use aoc_common::parsers::{groups, unsigned};
+use aoc_common::solution::{Answer, Solution};
+use log::trace;
+use nom::character::complete::line_ending;
+use nom::multi::separated_list1;
 use std::cmp::Reverse;
-use itertools::Itertools;
+use std::collections::BinaryHeap;
+
+pub struct Day01;
 
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path> {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+impl Solution for Day01 {
+    // Calories carried by each elf.
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        groups(input, separated_list1(line_ending, unsigned))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
 }
 
-fn part1(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let mut cur = 0;
+fn part1(input: &[Vec<u32>]) -> Result<u32, AocError> {
     let mut max = 0;
 
-    for line in input {
-        let line = line?;
-        println!("line: {}", line);
-        if line == "" {
-            if cur > max {
-                max = cur;
-            }
-            cur = 0;
-        } else {
-            let num = line.parse::<u32>().unwrap();
-            cur += num;
-        }
-    }
-    if cur > max {
-        max = cur;
+    for elf in input {
+        trace!("elf: {:?}", elf);
+        max = max.max(elf.iter().sum());
     }
 
     Ok(max)
 }
 
-/// Part 2
+// Part 2
 
 struct ConstGenericMinHeap<T: Ord, const N: usize> {
     heap: BinaryHeap<Reverse<T>>,
@@ -59,12 +59,8 @@ impl<T: Ord, const N: usize> ConstGenericMinHeap<T, N> {
     }
 }
 
-fn part2(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let inputs = input.group_by(|line| line.as_ref().unwrap().is_empty());
-    let inputs = inputs.into_iter().filter(|(key, _)| *key == false);
-    let inputs = inputs.into_iter().map(|(_, group)| {
-        group.map(|x| x.unwrap().parse::<u32>().unwrap()).sum::<u32>()
-    });
+fn part2(input: &[Vec<u32>]) -> Result<u32, AocError> {
+    let inputs = input.iter().map(|elf| elf.iter().sum::<u32>());
 
     let mut heap = ConstGenericMinHeap::<u32, 3>::new();
 
@@ -74,20 +70,3 @@ fn part2(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
 
     Ok(heap.heap.iter().map(|Reverse(e)| e).sum())
 }
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2022!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day01")?;
-    println!("Part 1: Num increases: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day01")?;
-    println!("Part 2: Num increases: {}", part2(lines)?);
-
-    Ok(())
-}
@@ -1,21 +1,29 @@
 extern crate nom;
 
+use aoc_common::error::AocError;
+use aoc_common::parsers::lines;
+use aoc_common::solution::{Answer, Solution};
 use nom::{
     branch::alt, bytes::complete::tag, character::complete::char, combinator::value,
     sequence::tuple, IResult,
 };
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
 
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -59,9 +67,9 @@ struct Round {
 
 #[derive(Debug, Clone)]
 enum RoundResult {
-    WIN,
-    LOSS,
-    DRAW,
+    Win,
+    Loss,
+    Draw,
 }
 
 fn challenge(input: &str) -> IResult<&str, Shape> {
@@ -93,24 +101,24 @@ fn round(input: &str) -> IResult<&str, Round> {
 
 fn eval_round(round: &Round) -> RoundResult {
     if round.challenge == round.response {
-        return RoundResult::DRAW;
+        return RoundResult::Draw;
     }
 
     if (round.challenge == Shape::Rock && round.response == Shape::Paper)
         || (round.challenge == Shape::Paper && round.response == Shape::Scissors)
         || (round.challenge == Shape::Scissors && round.response == Shape::Rock)
     {
-        return RoundResult::WIN;
+        return RoundResult::Win;
     }
 
-    RoundResult::LOSS
+    RoundResult::Loss
 }
 
 fn run_round(round: &Round) -> u32 {
     let score_outcome = match eval_round(round) {
-        RoundResult::WIN => 6,
-        RoundResult::DRAW => 3,
-        RoundResult::LOSS => 0,
+        RoundResult::Win => 6,
+        RoundResult::Draw => 3,
+        RoundResult::Loss => 0,
     };
     let score_shape = match round.response {
         Shape::Rock => 1,
@@ -121,15 +129,11 @@ fn run_round(round: &Round) -> u32 {
     score_outcome + score_shape
 }
 
-fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    input.try_fold(0, |total_score, line| {
-        let line = line?;
-        let (_, round) = round(&line).or(Err("could not parse line"))?;
-        Ok(total_score + run_round(&round))
-    })
+fn part1(input: &str) -> Result<u32, AocError> {
+    Ok(lines(input, round)?.iter().map(run_round).sum())
 }
 
-/// Part 2
+// Part 2
 
 #[derive(Debug, Clone)]
 struct Round2 {
@@ -139,9 +143,9 @@ struct Round2 {
 
 fn expected_outcome(input: &str) -> IResult<&str, RoundResult> {
     alt((
-        value(RoundResult::LOSS, char('X')),
-        value(RoundResult::DRAW, char('Y')),
-        value(RoundResult::WIN, char('Z')),
+        value(RoundResult::Loss, char('X')),
+        value(RoundResult::Draw, char('Y')),
+        value(RoundResult::Win, char('Z')),
     ))(input)
 }
 
@@ -159,13 +163,13 @@ fn round2(input: &str) -> IResult<&str, Round2> {
 
 fn determine_needed_shape(round: &Round2) -> Shape {
     match round.expected_outcome {
-        RoundResult::DRAW => round.challenge,
-        RoundResult::WIN => match round.challenge {
+        RoundResult::Draw => round.challenge,
+        RoundResult::Win => match round.challenge {
             Shape::Rock => Shape::Paper,
             Shape::Paper => Shape::Scissors,
             Shape::Scissors => Shape::Rock,
         },
-        RoundResult::LOSS => match round.challenge {
+        RoundResult::Loss => match round.challenge {
             Shape::Rock => Shape::Scissors,
             Shape::Paper => Shape::Rock,
             Shape::Scissors => Shape::Paper,
@@ -175,9 +179,9 @@ fn determine_needed_shape(round: &Round2) -> Shape {
 
 fn run_round2(round: &Round2) -> u32 {
     let score_outcome = match round.expected_outcome {
-        RoundResult::WIN => 6,
-        RoundResult::DRAW => 3,
-        RoundResult::LOSS => 0,
+        RoundResult::Win => 6,
+        RoundResult::Draw => 3,
+        RoundResult::Loss => 0,
     };
 
     let score_shape = match determine_needed_shape(round) {
@@ -189,27 +193,6 @@ fn run_round2(round: &Round2) -> u32 {
     score_outcome + score_shape
 }
 
-fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    input.try_fold(0, |total_score, line| {
-        let line = line?;
-        let (_, round) = round2(&line).or(Err("could not parse line"))?;
-        Ok(total_score + run_round2(&round))
-    })
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2022!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day02_example")?;
-    println!("Part 1: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day02")?;
-    println!("Part 2: {}", part2(lines)?);
-
-    Ok(())
+fn part2(input: &str) -> Result<u32, AocError> {
+    Ok(lines(input, round2)?.iter().map(run_round2).sum())
 }
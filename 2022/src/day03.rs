@@ -1,18 +1,25 @@
 use std::collections::HashSet;
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
 
+use aoc_common::error::AocError;
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
 
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
 }
 
 fn item_to_score(item: &char) -> u32 {
@@ -31,37 +38,31 @@ fn run_rucksack(line: &str) -> u32 {
     item_to_score(&common_item)
 }
 
-fn part1(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    Ok(input.map(|x| run_rucksack(&x.unwrap())).sum())
+fn part1(input: &[String]) -> Result<u32, AocError> {
+    Ok(input.iter().map(|x| run_rucksack(x)).sum())
 }
 
-/// Part 2
+// Part 2
 
 fn run_rucksack2(rucksacks: Vec<String>) -> u32 {
-    let mut iter = rucksacks.iter().map(|r| r.chars().collect::<HashSet<char>>());
-    let common_item = iter.next().map(|rucksack| iter.fold(rucksack, |set1, set2| &set1 & &set2)).unwrap().into_iter().next().unwrap();
+    let mut iter = rucksacks
+        .iter()
+        .map(|r| r.chars().collect::<HashSet<char>>());
+    let common_item = iter
+        .next()
+        .map(|rucksack| iter.fold(rucksack, |set1, set2| &set1 & &set2))
+        .unwrap()
+        .into_iter()
+        .next()
+        .unwrap();
     item_to_score(&common_item)
 }
 
-fn part2(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    Ok(input.chunks(3).into_iter().map(|rucksacks| {
-        run_rucksack2(rucksacks.map(|x| x.unwrap()).collect())
-    }).sum())
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2022!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day03")?;
-    println!("Part 1: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day03")?;
-    println!("Part 2: {}", part2(lines)?);
-
-    Ok(())
+fn part2(input: &[String]) -> Result<u32, AocError> {
+    Ok(input
+        .iter()
+        .chunks(3)
+        .into_iter()
+        .map(|rucksacks| run_rucksack2(rucksacks.cloned().collect()))
+        .sum())
 }
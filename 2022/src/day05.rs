@@ -1,26 +1,43 @@
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
+use aoc_common::error::AocError;
+use aoc_common::parsers::{parse_part, unsigned};
+use aoc_common::simulation::{run_to_end, Simulation};
+use aoc_common::solution::{Answer, Solution};
+use log::trace;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::anychar;
 use nom::combinator::{map, opt};
-use nom::sequence::{delimited, terminated};
+use nom::sequence::{delimited, terminated, tuple};
 use nom::IResult;
-use regex::Regex;
 
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = (State, Vec<Move>);
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
+
+    fn simulation(input: &Self::Input, part: u32) -> Result<Box<dyn Simulation + '_>, AocError> {
+        let step_fn = match part {
+            1 => move_one_by_one,
+            _ => move_at_once,
+        };
+        Ok(Box::new(Crane::new(input, step_fn)))
+    }
 }
 
-struct State {
+#[derive(Clone)]
+pub struct State {
     stacks: Vec<Vec<char>>,
 }
 
@@ -35,29 +52,31 @@ fn _empty(input: &str) -> IResult<&str, ()> {
 }
 
 fn elem(input: &str) -> IResult<&str, Option<char>> {
-    terminated(alt((map(_crate, |c| Some(c)), map(_empty, |_| None))), opt(tag(" ")))(input)
+    terminated(
+        alt((map(_crate, Some), map(_empty, |_| None))),
+        opt(tag(" ")),
+    )(input)
 }
 
-fn parse_initial_state(input: &mut io::Lines<io::BufReader<File>>) -> State {
+fn parse_initial_state<'a>(input: &mut impl Iterator<Item = &'a str>) -> State {
     let mut stacks = Vec::new();
 
     for line in input {
-        let line = line.unwrap();
-        println!("line: {:?}", line);
+        trace!("line: {:?}", line);
 
         if line.starts_with(" 1 ") {
             break;
         }
-        
+
         // Number of stacks is not known in advance and input lines have different lengths.
         let num_stacks = line.len() / 4 + 1;
         if num_stacks > stacks.len() {
             stacks.resize(num_stacks, Vec::new());
         }
 
-        let mut cur = &line[..];
+        let mut cur = line;
         let mut cur_stack = 0;
-        while let Ok((remaining, e)) = elem(&cur) {
+        while let Ok((remaining, e)) = elem(cur) {
             cur = remaining;
 
             if let Some(elem) = e {
@@ -75,75 +94,159 @@ fn parse_initial_state(input: &mut io::Lines<io::BufReader<File>>) -> State {
     State { stacks }
 }
 
-fn run<F>(input: &mut io::Lines<io::BufReader<File>>, step_fn: F) -> Result<String, Box<dyn Error>> 
-where F: Fn(usize, usize, usize, &mut State) {
-    let mut iter = input.into_iter();
+// from, to, num
+type Move = (usize, usize, usize);
+
+// num, from, to as written
+fn parse_move(input: &str) -> IResult<&str, (usize, usize, usize)> {
+    let (input, (_, num, _, from, _, to)) = tuple((
+        tag("move "),
+        unsigned,
+        tag(" from "),
+        unsigned,
+        tag(" to "),
+        unsigned,
+    ))(input)?;
+    Ok((input, (num, from, to)))
+}
 
-    let mut state = parse_initial_state(&mut iter);
+fn parse_input(input: &str) -> Result<(State, Vec<Move>), AocError> {
+    let mut iter = input.lines();
+
+    let state = parse_initial_state(&mut iter);
 
     // Expected empty line in input.
     iter.next();
 
+    let mut moves = Vec::new();
+
     for line in iter {
-        let line = line.unwrap();
-        
-        let re = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
-        let caps = re.captures(&line).unwrap();
+        let (num, from, to) = parse_part(input, line, parse_move)?;
 
-        let from = caps.get(2).unwrap().as_str().parse::<usize>().unwrap() - 1;
-        let to = caps.get(3).unwrap().as_str().parse::<usize>().unwrap() - 1;
-        let num = caps.get(1).unwrap().as_str().parse::<usize>().unwrap();
+        // Stacks are numbered from 1.
+        let from = from.checked_sub(1).ok_or("no stack 0")?;
+        let to = to.checked_sub(1).ok_or("no stack 0")?;
 
-        step_fn(from, to, num, &mut state);
+        moves.push((from, to, num));
     }
 
-    let mut result = String::with_capacity(state.stacks.len());
-    for stack in state.stacks {
-        result.push(*(&stack).last().unwrap());
+    Ok((state, moves))
+}
+
+impl State {
+    // Draws the stacks the way the puzzle does, bottom crates at the bottom.
+    fn render(&self) -> String {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut out = String::new();
+
+        for level in (0..height).rev() {
+            let row: Vec<String> = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            out.push_str(row.join(" ").trim_end());
+            out.push('\n');
+        }
+
+        let numbers: Vec<String> = (1..=self.stacks.len())
+            .map(|n| format!(" {} ", n))
+            .collect();
+        out.push_str(&numbers.join(" "));
+        out.push('\n');
+
+        out
     }
+}
 
-    Ok(result)
+type StepFn = fn(usize, usize, usize, &mut State);
+
+// Moves the crates one move per step.
+struct Crane<'a> {
+    state: State,
+    moves: &'a [Move],
+    done: usize,
+    step_fn: StepFn,
 }
 
-fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<String, Box<dyn Error>> {
-    run(&mut input, |from, to, num, state| {
-        for _ in 0..num {
-            let popped = state.stacks[from].pop().unwrap();
-            state.stacks[to].push(popped);
+impl<'a> Crane<'a> {
+    fn new((state, moves): &'a (State, Vec<Move>), step_fn: StepFn) -> Crane<'a> {
+        Crane {
+            state: state.clone(),
+            moves,
+            done: 0,
+            step_fn,
         }
-    })
+    }
 }
 
-/// Part 2
+impl Simulation for Crane<'_> {
+    fn step(&mut self) -> bool {
+        let Some(&(from, to, num)) = self.moves.get(self.done) else {
+            return false;
+        };
 
-fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<String, Box<dyn Error>> {
-    run(&mut input, |from, to, num, state| {
-        let range_to_drain = (state.stacks[from].len() - num)..;
-        // .collect() is used here because drain() keeps a mutable reference to the stack which in turn
-        // keeps a mutable reference to the overall stacks Vec (through Index). That prevents getting
-        // an immutable ref to state.stacks[from] and a mutable ref to state.stacks[to] at the same time.
-        // Alternative in order to avoid collect(): Use split(). But that requires some arithmetic on the
-        // indexes and reduces the readability here.
-        let drained = state.stacks[from].drain(range_to_drain).collect::<Vec<_>>();
-        state.stacks[to].extend(drained);
-    })
+        (self.step_fn)(from, to, num, &mut self.state);
+        self.done += 1;
+
+        true
+    }
+
+    fn render(&self) -> String {
+        let next = match self.moves.get(self.done) {
+            Some((from, to, num)) => format!("next: move {} from {} to {}", num, from + 1, to + 1),
+            None => "done".to_string(),
+        };
+
+        format!(
+            "{} of {} moves, {}\n\n{}",
+            self.done,
+            self.moves.len(),
+            next,
+            self.state.render()
+        )
+    }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2022!");
+fn run(input: &(State, Vec<Move>), step_fn: StepFn) -> Result<String, AocError> {
+    let mut crane = Crane::new(input, step_fn);
+    run_to_end(&mut crane);
 
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
+    let mut result = String::with_capacity(crane.state.stacks.len());
+    for stack in crane.state.stacks {
+        result.push(*stack.last().unwrap());
+    }
 
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day05")?;
-    // let lines = read_input("src/input/day05")?;
-    println!("Part 1: {}", part1(lines)?);
+    Ok(result)
+}
 
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day05")?;
-    // let lines = read_input("src/input/day05")?;
-    println!("Part 2: {}", part2(lines)?);
+fn move_one_by_one(from: usize, to: usize, num: usize, state: &mut State) {
+    for _ in 0..num {
+        let popped = state.stacks[from].pop().unwrap();
+        state.stacks[to].push(popped);
+    }
+}
+
+fn part1(input: &(State, Vec<Move>)) -> Result<String, AocError> {
+    run(input, move_one_by_one)
+}
+
+// Part 2
+
+fn move_at_once(from: usize, to: usize, num: usize, state: &mut State) {
+    let range_to_drain = (state.stacks[from].len() - num)..;
+    // .collect() is used here because drain() keeps a mutable reference to the stack which in turn
+    // keeps a mutable reference to the overall stacks Vec (through Index). That prevents getting
+    // an immutable ref to state.stacks[from] and a mutable ref to state.stacks[to] at the same time.
+    // Alternative in order to avoid collect(): Use split(). But that requires some arithmetic on the
+    // indexes and reduces the readability here.
+    let drained = state.stacks[from].drain(range_to_drain).collect::<Vec<_>>();
+    state.stacks[to].extend(drained);
+}
 
-    Ok(())
+fn part2(input: &(State, Vec<Move>)) -> Result<String, AocError> {
+    run(input, move_at_once)
 }
@@ -1,19 +1,26 @@
-use std::collections::{VecDeque, HashMap};
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+use aoc_common::error::AocError;
+use aoc_common::solution::{Answer, Solution};
+use std::collections::{HashMap, VecDeque};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(input.lines().next().ok_or("empty input")?.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
 }
 
-fn run(input: &str, size: usize) -> Result<usize, Box<dyn Error>> {
+fn run(input: &str, size: usize) -> Result<usize, AocError> {
     let mut in_window: HashMap<char, u32> = HashMap::new();
     let mut buf: VecDeque<char> = VecDeque::with_capacity(size);
 
@@ -33,7 +40,7 @@ fn run(input: &str, size: usize) -> Result<usize, Box<dyn Error>> {
 
         match in_window.get(&c) {
             Some(n) => in_window.insert(c, n + 1),
-            None => in_window.insert(c, 1)
+            None => in_window.insert(c, 1),
         };
 
         buf.push_back(c);
@@ -43,93 +50,83 @@ fn run(input: &str, size: usize) -> Result<usize, Box<dyn Error>> {
         }
     }
 
-    Err("Could not find marker".into())    
+    Err("Could not find marker".into())
 }
 
 #[test]
-fn run_a() -> Result<(), Box<dyn Error>> {
+fn run_a() -> Result<(), AocError> {
     assert_eq!(run("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4)?, 7);
     Ok(())
 }
 
 #[test]
-fn run_b() -> Result<(), Box<dyn Error>> {
+fn run_b() -> Result<(), AocError> {
     assert_eq!(run("bvwbjplbgvbhsrlpgdmjqwftvncz", 4)?, 5);
     Ok(())
 }
 
 #[test]
-fn run_c() -> Result<(), Box<dyn Error>> {
+fn run_c() -> Result<(), AocError> {
     assert_eq!(run("nppdvjthqldpwncqszvftbrmjlhg", 4)?, 6);
     Ok(())
 }
 
 #[test]
-fn run_d() -> Result<(), Box<dyn Error>> {
+fn run_d() -> Result<(), AocError> {
     assert_eq!(run("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4)?, 10);
     Ok(())
 }
 
 #[test]
-fn run_e() -> Result<(), Box<dyn Error>> {
+fn run_e() -> Result<(), AocError> {
     assert_eq!(run("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4)?, 11);
     Ok(())
 }
 
-fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<usize, Box<dyn Error>> {
-    run(&input.next().unwrap().unwrap(), 4)
+fn part1(input: &str) -> Result<usize, AocError> {
+    run(input, 4)
 }
-/// Part 2
+// Part 2
 
 #[test]
-fn run2_a() -> Result<(), Box<dyn Error>> {
+fn run2_a() -> Result<(), AocError> {
     assert_eq!(run("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14)?, 19);
     Ok(())
 }
 
 #[test]
-fn run2_b() -> Result<(), Box<dyn Error>> {
+fn run2_b() -> Result<(), AocError> {
     assert_eq!(run("bvwbjplbgvbhsrlpgdmjqwftvncz", 14)?, 23);
     Ok(())
 }
 
 #[test]
-fn run2_c() -> Result<(), Box<dyn Error>> {
+fn run2_c() -> Result<(), AocError> {
     assert_eq!(run("nppdvjthqldpwncqszvftbrmjlhg", 14)?, 23);
     Ok(())
 }
 
 #[test]
-fn run2_d() -> Result<(), Box<dyn Error>> {
+fn run2_d() -> Result<(), AocError> {
     assert_eq!(run("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14)?, 29);
     Ok(())
 }
 
 #[test]
-fn run2_e() -> Result<(), Box<dyn Error>> {
+fn run2_e() -> Result<(), AocError> {
     assert_eq!(run("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14)?, 26);
     Ok(())
 }
 
-fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<usize, Box<dyn Error>> {
-    run(&input.next().unwrap().unwrap(), 14)
+fn part2(input: &str) -> Result<usize, AocError> {
+    run(input, 14)
 }
 
-
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2022!");
-
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day06")?;
-    println!("Part 1: {}", part1(lines)?);
-
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day06")?;
-    // let lines = read_input("src/input/day06")?;
-    println!("Part 2: {}", part2(lines)?);
-
+#[test]
+fn test_solve() -> Result<(), AocError> {
+    let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n";
+    assert_eq!(Day06::solve(input, 1)?, Answer::Unsigned(7));
+    assert_eq!(Day06::solve(input, 2)?, Answer::Unsigned(19));
+    assert!(Day06::solve(input, 3).is_err());
     Ok(())
 }
@@ -1,11 +1,14 @@
 use std::cell::RefCell;
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use std::collections::HashSet;
 use std::rc::Rc;
 
+use aoc_common::dot::Dot;
+use aoc_common::error::AocError;
+use aoc_common::parsers::lines;
+#[cfg(test)]
+use aoc_common::parsers::parse_all;
+use aoc_common::random::Rng;
+use aoc_common::solution::{Answer, Solution};
 use nom::branch::alt;
 use nom::bytes::complete::is_not;
 use nom::bytes::complete::tag;
@@ -14,15 +17,35 @@ use nom::sequence::preceded;
 use nom::sequence::separated_pair;
 use nom::IResult;
 
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Rc<AocDirent>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
+
+    fn dot(input: &Self::Input) -> Result<Dot, AocError> {
+        let mut dot = Dot::digraph();
+        add_to_dot(&mut dot, input, &mut 0);
+        Ok(dot)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, AocError> {
+        Ok(generate(rng, size))
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 enum Line {
     ChangeDir { target: String },
     Directory { name: String },
@@ -35,9 +58,9 @@ fn name(input: &str) -> IResult<&str, &str> {
 }
 
 fn change_dir(input: &str) -> IResult<&str, Line> {
-    let (_, target) = preceded(tag("$ cd "), name)(input)?;
+    let (input, target) = preceded(tag("$ cd "), name)(input)?;
     Ok((
-        "",
+        input,
         Line::ChangeDir {
             target: target.to_string(),
         },
@@ -45,14 +68,14 @@ fn change_dir(input: &str) -> IResult<&str, Line> {
 }
 
 fn ls(input: &str) -> IResult<&str, Line> {
-    let (_, _) = tag("$ ls")(input)?;
-    Ok(("", Line::Ls))
+    let (input, _) = tag("$ ls")(input)?;
+    Ok((input, Line::Ls))
 }
 
 fn directory(input: &str) -> IResult<&str, Line> {
-    let (_, name) = preceded(tag("dir "), name)(input)?;
+    let (input, name) = preceded(tag("dir "), name)(input)?;
     Ok((
-        "",
+        input,
         Line::Directory {
             name: name.to_string(),
         },
@@ -60,9 +83,9 @@ fn directory(input: &str) -> IResult<&str, Line> {
 }
 
 fn file(input: &str) -> IResult<&str, Line> {
-    let (_, (size, name)) = separated_pair(u32, tag(" "), name)(input)?;
+    let (input, (size, name)) = separated_pair(u32, tag(" "), name)(input)?;
     Ok((
-        "",
+        input,
         Line::File {
             name: name.to_string(),
             size,
@@ -70,14 +93,12 @@ fn file(input: &str) -> IResult<&str, Line> {
     ))
 }
 
-fn parse_line(input: &str) -> Result<Line, Box<dyn Error>> {
-    let (_, line) =
-        alt((change_dir, ls, directory, file))(input).or(Err("could not parse line"))?;
-    Ok(line)
+fn parse_line(input: &str) -> IResult<&str, Line> {
+    alt((change_dir, ls, directory, file))(input)
 }
 
 #[derive(Debug)]
-struct AocDirent {
+pub struct AocDirent {
     name: String,
     data: AocData,
 }
@@ -103,12 +124,12 @@ impl AocDir {
     fn new(parent: Option<Rc<AocDir>>) -> AocDir {
         AocDir {
             dirents: RefCell::new(Vec::new()),
-            parent: parent,
+            parent,
         }
     }
 }
 
-fn parse_input(input: io::Lines<io::BufReader<File>>) -> Result<Rc<AocDirent>, Box<dyn Error>> {
+fn parse_input(input: &str) -> Result<Rc<AocDirent>, AocError> {
     let root_directory = Rc::new(AocDir::new(None));
     let root_dirent = Rc::new(AocDirent {
         name: "/".to_string(),
@@ -118,11 +139,8 @@ fn parse_input(input: io::Lines<io::BufReader<File>>) -> Result<Rc<AocDirent>, B
     let mut cwd = root_directory.clone();
     let mut cwd_str = vec![root_dirent.name.clone()];
 
-    for line in input {
-        let line = line?;
-        let foo = parse_line(&line)?;
-
-        match &foo {
+    for parsed in lines(input, parse_line)? {
+        match &parsed {
             Line::ChangeDir { target } => {
                 if target == "/" {
                     cwd = if let AocData::Dir(dir) = &root_dirent.data {
@@ -132,7 +150,10 @@ fn parse_input(input: io::Lines<io::BufReader<File>>) -> Result<Rc<AocDirent>, B
                     };
                     cwd_str = vec![root_dirent.name.clone()];
                 } else if target == ".." {
-                    cwd = cwd.parent.as_ref().unwrap().clone();
+                    cwd = cwd
+                        .parent
+                        .clone()
+                        .ok_or("attempted to cd out of the root")?;
                     cwd_str.pop();
                 } else {
                     let mut target_dir = None;
@@ -179,6 +200,105 @@ fn parse_input(input: io::Lines<io::BufReader<File>>) -> Result<Rc<AocDirent>, B
     Ok(root_dirent)
 }
 
+// A tree to be turned into a transcript by `generate`.
+enum GeneratedEntry {
+    File(String, u32),
+    Dir(String, Vec<GeneratedEntry>),
+}
+
+const TOTAL_DISK_SPACE: u32 = 70_000_000;
+const REQUIRED_FREE_SPACE: u32 = 30_000_000;
+
+// Up to `budget` entries for one directory, with names unique within it. Files are at most
+// `max_file` large.
+fn generate_dir(
+    rng: &mut Rng,
+    budget: &mut usize,
+    max_file: u64,
+    depth: usize,
+) -> Vec<GeneratedEntry> {
+    let mut entries = Vec::new();
+    let mut names = HashSet::new();
+    // The root takes whatever its subdirectories leave over.
+    let count = if depth == 0 {
+        usize::MAX
+    } else {
+        rng.range(0..=6) as usize
+    };
+
+    while entries.len() < count && *budget > 0 {
+        *budget -= 1;
+
+        let name: String = (0..rng.range(1..=8))
+            .map(|_| char::from(b'a' + rng.range(0..=25) as u8))
+            .collect();
+        if !names.insert(name.clone()) {
+            continue;
+        }
+
+        if depth < 8 && rng.chance(0.3) {
+            let dir = generate_dir(rng, budget, max_file, depth + 1);
+            entries.push(GeneratedEntry::Dir(name, dir));
+        } else if rng.chance(0.5) {
+            entries.push(GeneratedEntry::File(name, rng.range(1..=max_file) as u32));
+        } else {
+            let extension = rng.choose(&["txt", "dat", "log", "lst"]);
+            let name = format!("{}.{}", name, extension);
+            entries.push(GeneratedEntry::File(name, rng.range(1..=max_file) as u32));
+        }
+    }
+
+    entries
+}
+
+fn generated_size(entries: &[GeneratedEntry]) -> u32 {
+    entries
+        .iter()
+        .map(|entry| match entry {
+            GeneratedEntry::File(_, size) => *size,
+            GeneratedEntry::Dir(_, entries) => generated_size(entries),
+        })
+        .sum()
+}
+
+fn write_transcript(entries: &[GeneratedEntry], out: &mut String) {
+    out.push_str("$ ls\n");
+    for entry in entries {
+        match entry {
+            GeneratedEntry::File(name, size) => out.push_str(&format!("{} {}\n", size, name)),
+            GeneratedEntry::Dir(name, _) => out.push_str(&format!("dir {}\n", name)),
+        }
+    }
+
+    for entry in entries {
+        if let GeneratedEntry::Dir(name, entries) = entry {
+            out.push_str(&format!("$ cd {}\n", name));
+            write_transcript(entries, out);
+            out.push_str("$ cd ..\n");
+        }
+    }
+}
+
+// A transcript exploring a random tree of about `size` files and directories. File sizes shrink
+// for large trees so that everything still fits on the disk, and a large file in the root makes
+// sure the disk is full enough for part 2.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut budget = size.max(1);
+    let max_file = (60_000_000 / budget as u64).clamp(1, 400_000);
+    let mut root = generate_dir(rng, &mut budget, max_file, 0);
+
+    let used = generated_size(&root);
+    let full = TOTAL_DISK_SPACE - REQUIRED_FREE_SPACE;
+    if used <= full {
+        let size = full - used + rng.range(1..=5_000_000) as u32;
+        root.push(GeneratedEntry::File("padding.bin".to_string(), size));
+    }
+
+    let mut out = "$ cd /\n".to_string();
+    write_transcript(&root, &mut out);
+    out
+}
+
 // Alternatives considered:
 // * Calculate the sizes and the score at the same time in the same walk. But that's messy.
 //   Keep the concerns separate, even it means performing re-calculations.
@@ -216,9 +336,10 @@ where
     }
 }
 
-fn dirent_size(dirent: &AocDirent) -> u32 {
+// Files have u32 sizes, many of them together can take more.
+fn dirent_size(dirent: &AocDirent) -> u64 {
     match &dirent.data {
-        AocData::File(file) => file.size,
+        AocData::File(file) => file.size.into(),
         AocData::Dir(dir) => dir
             .dirents
             .borrow()
@@ -227,12 +348,37 @@ fn dirent_size(dirent: &AocDirent) -> u32 {
     }
 }
 
-fn part1(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let root_dirent = parse_input(input)?;
+// Adds `dirent` and everything below it, labelled with their (total) sizes. Returns the id of
+// `dirent`.
+fn add_to_dot(dot: &mut Dot, dirent: &AocDirent, next_id: &mut usize) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+
+    match &dirent.data {
+        AocData::File(file) => {
+            let label = format!("{}\n{}", dirent.name, file.size);
+            dot.node(id, &[("label", &label), ("shape", "note")]);
+        }
+        AocData::Dir(dir) => {
+            // The root is called "/" already.
+            let name = dirent.name.trim_end_matches('/');
+            let label = format!("{}/\n{}", name, dirent_size(dirent));
+            dot.node(id, &[("label", &label), ("shape", "folder")]);
+
+            for child in &*dir.dirents.borrow() {
+                let child = add_to_dot(dot, child, next_id);
+                dot.edge(id, child, &[]);
+            }
+        }
+    }
+
+    id
+}
 
+fn part1(root_dirent: &AocDirent) -> Result<u64, AocError> {
     let mut sum = 0;
 
-    walk(&root_dirent, &mut |dirent| {
+    walk(root_dirent, &mut |dirent| {
         if let AocData::Dir(_) = dirent.data {
             let size = dirent_size(dirent);
             if size < 100_000 {
@@ -244,47 +390,90 @@ fn part1(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
     Ok(sum)
 }
 
-/// Part 2
-
-fn part2(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let root_dirent = parse_input(input)?;
+// Part 2
 
-    let outermost_size = dirent_size(&root_dirent);
+fn part2(root_dirent: &AocDirent) -> Result<u64, AocError> {
+    let outermost_size = dirent_size(root_dirent);
 
-    const TOTAL_DISK_SPACE: u32 = 70_000_000;
-    const REQUIRED_FREE_SPACE: u32 = 30_000_000;
+    let free_space = u64::from(TOTAL_DISK_SPACE)
+        .checked_sub(outermost_size)
+        .ok_or("the files take more than the whole disk")?;
+    let need_to_delete = u64::from(REQUIRED_FREE_SPACE)
+        .checked_sub(free_space)
+        .ok_or("there is enough free space already")?;
 
-    let need_to_delete = REQUIRED_FREE_SPACE - (TOTAL_DISK_SPACE - outermost_size);
+    let mut smallest_directory_size = None;
 
-    let mut smallest_directory_size = u32::MAX;
-
-    walk(&root_dirent, &mut |dirent| {
+    walk(root_dirent, &mut |dirent| {
         if let AocData::Dir(_) = dirent.data {
             let size = dirent_size(dirent);
-            if size > need_to_delete && size < smallest_directory_size {
-                smallest_directory_size = size;
+            if size > need_to_delete
+                && smallest_directory_size.is_none_or(|smallest| size < smallest)
+            {
+                smallest_directory_size = Some(size);
             }
         }
     });
 
-    Ok(smallest_directory_size)
+    Ok(smallest_directory_size.ok_or("no directory is large enough")?)
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2022!");
+#[test]
+fn test_disk_space() {
+    let root = parse_input("$ cd /\n$ ls\ndir a\n1 b\n$ cd a\n$ ls\n2 c\n").unwrap();
+    assert_eq!(part1(&root).unwrap(), 5);
+    assert!(part2(&root).is_err());
 
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
+    let root = parse_input("$ cd /\n$ ls\n4294967295 a\n4294967295 b\n").unwrap();
+    assert_eq!(part1(&root).unwrap(), 0);
+    assert!(part2(&root).is_err());
+}
 
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day07")?;
-    // let lines = read_input("src/input/day07")?;
-    println!("Part 1: {}", part1(lines)?);
+#[test]
+fn test_parse_trailing() {
+    assert!(parse_input("$ cd /\n$ ls\ndir a\n14848514 b.txt\n").is_ok());
+    for (input, column) in [("$ lsxyz\n", 5), ("$ cd /\n$ cd a b\n", 7), ("12 a b\n", 5)] {
+        assert!(matches!(
+            parse_input(input),
+            Err(AocError::Parse(e)) if e.column == column
+        ));
+    }
+}
 
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day07")?;
-    // let lines = read_input("src/input/day07")?;
-    println!("Part 2: {}", part2(lines)?);
+// `line` the way the terminal output shows it.
+#[cfg(test)]
+fn write_line(line: &Line) -> String {
+    match line {
+        Line::ChangeDir { target } => format!("$ cd {}", target),
+        Line::Directory { name } => format!("dir {}", name),
+        Line::File { name, size } => format!("{} {}", size, name),
+        Line::Ls => "$ ls".to_string(),
+    }
+}
+
+#[cfg(test)]
+fn transcript_lines() -> impl proptest::strategy::Strategy<Value = Line> {
+    use proptest::prelude::*;
+
+    let name = "[^ \r\n]{1,12}";
+    prop_oneof![
+        name.prop_map(|target| Line::ChangeDir { target }),
+        name.prop_map(|name| Line::Directory { name }),
+        (name, any::<u32>()).prop_map(|(name, size)| Line::File { name, size }),
+        Just(Line::Ls),
+    ]
+}
 
-    Ok(())
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parse_roundtrip(line in transcript_lines()) {
+        let text = write_line(&line);
+        proptest::prop_assert_eq!(parse_all(&text, parse_line).unwrap(), line);
+    }
+
+    #[test]
+    fn test_parse_anything(input: String) {
+        let _ = parse_line(&input);
+    }
 }
@@ -1,31 +1,49 @@
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
+use aoc_common::error::AocError;
+use aoc_common::grid::Grid;
+use aoc_common::parsers::lines;
+use aoc_common::simulation::{run_to_end, Simulation};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::sparse::{Point, SparseGrid};
+use aoc_common::visualize::{self, Rgb, GREY, NAVY, RED, WHITE, YELLOW};
+use log::{log_enabled, trace, Level};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
+use nom::character::complete::i32;
 use nom::sequence::preceded;
 use nom::IResult;
-use nom::character::complete::i32;
-use std::collections::HashSet;
 
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Action>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        lines(input, parse_action)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
+
+    fn simulation(input: &Self::Input, part: u32) -> Result<Box<dyn Simulation + '_>, AocError> {
+        match part {
+            1 => Ok(Box::new(Rope::<2>::new(input))),
+            _ => Ok(Box::new(Rope::<10>::new(input))),
+        }
+    }
 }
 
-#[derive(Debug)]
-struct Action {
+#[derive(Debug, Clone, PartialEq)]
+pub struct Action {
     direction: Direction,
     steps: i32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 enum Direction {
     Up,
     Right,
@@ -35,57 +53,76 @@ enum Direction {
 
 fn parse_action_up(input: &str) -> IResult<&str, Action> {
     let (input, num) = preceded(tag("U "), i32)(input)?;
-    Ok((input, Action { direction: Direction::Up, steps: num }))
+    Ok((
+        input,
+        Action {
+            direction: Direction::Up,
+            steps: num,
+        },
+    ))
 }
 
 fn parse_action_left(input: &str) -> IResult<&str, Action> {
     let (input, num) = preceded(tag("L "), i32)(input)?;
-    Ok((input, Action { direction: Direction::Left, steps: num }))
+    Ok((
+        input,
+        Action {
+            direction: Direction::Left,
+            steps: num,
+        },
+    ))
 }
 
 fn parse_action_right(input: &str) -> IResult<&str, Action> {
     let (input, num) = preceded(tag("R "), i32)(input)?;
-    Ok((input, Action { direction: Direction::Right, steps: num }))
+    Ok((
+        input,
+        Action {
+            direction: Direction::Right,
+            steps: num,
+        },
+    ))
 }
 
 fn parse_action_down(input: &str) -> IResult<&str, Action> {
     let (input, num) = preceded(tag("D "), i32)(input)?;
-    Ok((input, Action { direction: Direction::Down, steps: num }))
+    Ok((
+        input,
+        Action {
+            direction: Direction::Down,
+            steps: num,
+        },
+    ))
 }
 
 fn parse_action(input: &str) -> IResult<&str, Action> {
-    alt((parse_action_up, parse_action_left, parse_action_right, parse_action_down))(input)
-}
-
-// We need Pos to be Copy to make initializing [Pos; N] easier.
-// Would be cool to rely on Pos: Default, but [T; N] for a const generic N is not Default yet at the time of writing.
-// https://rust-lang.github.io/project-const-generics/vision/status_quo/array_default.html
-#[derive(Eq, Hash, PartialEq, Clone, Copy, Debug, Default)]
-struct Pos {
-    x: i32,
-    y: i32,
+    alt((
+        parse_action_up,
+        parse_action_left,
+        parse_action_right,
+        parse_action_down,
+    ))(input)
 }
 
 struct State<const N: usize> {
-    knots: [Pos; N],
-    // Coordinates can become negative (making indexing into a 2d array rather painful),
-    // the world is rather sparsely populated, and we need cheap lookups, so a hash set seems
-    // like a good trade-off.
-    visited_positions: HashSet<Pos>,
+    knots: [Point; N],
+    // Coordinates can become negative and the world is rather sparsely populated.
+    visited_positions: SparseGrid<char>,
 }
 
 impl<const N: usize> State<N> {
-
     fn new() -> State<N> {
         State {
-            knots: [Pos { x: 0, y: 0 }; N],
-            visited_positions: HashSet::new(),
+            knots: [(0, 0); N],
+            visited_positions: SparseGrid::new(),
         }
     }
 
     fn knots_touching(&self, i: usize, j: usize) -> bool {
-        self.knots[j].x >= self.knots[i].x - 1 && self.knots[j].x <= self.knots[i].x + 1 &&
-        self.knots[j].y >= self.knots[i].y - 1 && self.knots[j].y <= self.knots[i].y + 1
+        self.knots[j].0 >= self.knots[i].0 - 1
+            && self.knots[j].0 <= self.knots[i].0 + 1
+            && self.knots[j].1 >= self.knots[i].1 - 1
+            && self.knots[j].1 <= self.knots[i].1 + 1
     }
 
     fn step_all_knots(&mut self) {
@@ -93,17 +130,17 @@ impl<const N: usize> State<N> {
             let follower = i;
             let leader = i - 1;
 
-            while ! self.knots_touching(leader, follower) {
-                if self.knots[follower].x < self.knots[leader].x {
-                    self.knots[follower].x += 1;
-                } else if self.knots[follower].x > self.knots[leader].x {
-                    self.knots[follower].x -= 1;
+            while !self.knots_touching(leader, follower) {
+                if self.knots[follower].0 < self.knots[leader].0 {
+                    self.knots[follower].0 += 1;
+                } else if self.knots[follower].0 > self.knots[leader].0 {
+                    self.knots[follower].0 -= 1;
                 }
 
-                if self.knots[follower].y < self.knots[leader].y {
-                    self.knots[follower].y += 1;
-                } else if self.knots[follower].y > self.knots[leader].y {
-                    self.knots[follower].y -= 1;
+                if self.knots[follower].1 < self.knots[leader].1 {
+                    self.knots[follower].1 += 1;
+                } else if self.knots[follower].1 > self.knots[leader].1 {
+                    self.knots[follower].1 -= 1;
                 }
 
                 if i == N - 1 {
@@ -117,91 +154,178 @@ impl<const N: usize> State<N> {
         for _ in 0..action.steps {
             match action.direction {
                 Direction::Up => {
-                    self.knots[0].y += 1;
+                    self.knots[0].1 -= 1;
                 }
                 Direction::Left => {
-                    self.knots[0].x -= 1;
+                    self.knots[0].0 -= 1;
                 }
                 Direction::Right => {
-                    self.knots[0].x += 1;
+                    self.knots[0].0 += 1;
                 }
                 Direction::Down => {
-                    self.knots[0].y -= 1;
+                    self.knots[0].1 += 1;
                 }
-            }        
+            }
             self.step_all_knots();
         }
     }
 
     fn mark_tail_position_as_visited(&mut self) {
-        self.visited_positions.insert(self.knots[N - 1].clone());
+        self.visited_positions.insert(self.knots[N - 1], '#');
     }
 
     fn num_unique_visited_positions(&self) -> u32 {
         u32::try_from(self.visited_positions.len()).unwrap()
     }
 
-    // Very hacky.
-    fn print(&self) {
-        let mut field = [['.'; 50]; 50];
-    
-        for (i, knot) in self.knots.iter().enumerate() {
-            field[usize::try_from(knot.x + 25).unwrap()][usize::try_from(knot.y + 25).unwrap()] = char::from_digit(i.try_into().unwrap(), 10).unwrap();
+    // The visited positions with the knots on top, the head as 0.
+    fn field(&self) -> SparseGrid<char> {
+        let mut field = self.visited_positions.clone();
+        for (i, &knot) in self.knots.iter().enumerate().rev() {
+            field.insert(knot, char::from_digit(i as u32, 36).unwrap());
         }
-    
-        for i in 0..50 {
-            for j in 0..50 {
-                print!("{}", field[j][50 - i - 1]);
-            }
-            println!();
+
+        field
+    }
+
+    fn trace(&self) {
+        if log_enabled!(Level::Trace) {
+            trace!("\n{}", self.field());
         }
     }
+
+    // The visited positions in grey, the head white, the tail red and the knots in between yellow.
+    fn draw(&self, (top_left, bottom_right): (Point, Point)) -> Grid<Rgb> {
+        visualize::draw_sparse(
+            &self.visited_positions,
+            top_left,
+            bottom_right,
+            |point, visited| match self.knots.iter().position(|&knot| knot == point) {
+                Some(0) => WHITE,
+                Some(i) if i == N - 1 => RED,
+                Some(_) => YELLOW,
+                None if visited.is_some() => GREY,
+                None => NAVY,
+            },
+        )
+    }
 }
 
-fn part1(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let mut state = State::<2>::new();
-    state.mark_tail_position_as_visited();
+// The knots never leave the area the head moves in, frames of that size show the whole rope all
+// the time. A rope of one knot tracks where the head went.
+fn head_area(input: &[Action]) -> (Point, Point) {
+    let mut head = State::<1>::new();
+    head.mark_tail_position_as_visited();
 
-    for line in input {
-        let line = line?;
-        let (_, action) = parse_action(&line).or(Err("could not parse action"))?;
-        state.feed(&action);
+    for action in input {
+        head.feed(action);
+        head.mark_tail_position_as_visited();
     }
-    
-    Ok(state.num_unique_visited_positions())
+
+    head.visited_positions.bounds().unwrap()
 }
 
-/// Part 2
+// One action per step.
+struct Rope<'a, const N: usize> {
+    state: State<N>,
+    actions: &'a [Action],
+    done: usize,
+    // Only needed for frames.
+    area: Option<(Point, Point)>,
+}
 
-fn part2(input: io::Lines<io::BufReader<File>>) -> Result<u32, Box<dyn Error>> {
-    let mut state = State::<10>::new();
-    state.mark_tail_position_as_visited();
+impl<'a, const N: usize> Rope<'a, N> {
+    fn new(actions: &'a [Action]) -> Rope<'a, N> {
+        let mut state = State::new();
+        state.mark_tail_position_as_visited();
 
-    for line in input {
-        let line = line?;
-        let (_, action) = parse_action(&line).or(Err("could not parse action"))?;
-        state.feed(&action);
+        Rope {
+            state,
+            actions,
+            done: 0,
+            area: visualize::is_recording().then(|| head_area(actions)),
+        }
     }
-    
-    Ok(state.num_unique_visited_positions())
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2022!");
+impl<const N: usize> Simulation for Rope<'_, N> {
+    fn step(&mut self) -> bool {
+        let Some(action) = self.actions.get(self.done) else {
+            return false;
+        };
 
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
+        self.state.feed(action);
+        self.done += 1;
 
-    // TODO: Refactor how to find the file.
-    // let lines = read_input("src/input/day09_example")?;
-    let lines = read_input("src/input/day09")?;
-    println!("Part 1: {}", part1(lines)?);
+        if let Some(area) = self.area {
+            visualize::frame(|| self.state.draw(area));
+        }
 
-    // TODO: Refactor how to find the file.
-    // let lines = read_input("src/input/day09_example2")?;
-    let lines = read_input("src/input/day09")?;
-    println!("Part 2: {}", part2(lines)?);
+        true
+    }
 
-    // 2386
-    Ok(())
+    // The whole field gets too large for a terminal, only the surroundings of the head are shown.
+    fn render(&self) -> String {
+        let (x, y) = self.state.knots[0];
+        format!(
+            "{} of {} actions, {} positions visited\n\n{}",
+            self.done,
+            self.actions.len(),
+            self.state.num_unique_visited_positions(),
+            self.state
+                .field()
+                .render_area((x - 30, y - 12), (x + 30, y + 12))
+        )
+    }
+}
+
+fn part1(input: &[Action]) -> Result<u32, AocError> {
+    let mut rope = Rope::<2>::new(input);
+    run_to_end(&mut rope);
+    rope.state.trace();
+
+    Ok(rope.state.num_unique_visited_positions())
+}
+
+// Part 2
+
+fn part2(input: &[Action]) -> Result<u32, AocError> {
+    let mut rope = Rope::<10>::new(input);
+    run_to_end(&mut rope);
+    rope.state.trace();
+
+    Ok(rope.state.num_unique_visited_positions())
+}
+
+#[cfg(test)]
+fn actions() -> impl proptest::strategy::Strategy<Value = Action> {
+    use proptest::prelude::*;
+
+    let direction = prop_oneof![
+        Just(Direction::Up),
+        Just(Direction::Right),
+        Just(Direction::Down),
+        Just(Direction::Left),
+    ];
+    (direction, any::<i32>()).prop_map(|(direction, steps)| Action { direction, steps })
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parse_roundtrip(action in actions()) {
+        let direction = match action.direction {
+            Direction::Up => 'U',
+            Direction::Right => 'R',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+        };
+        let text = format!("{} {}", direction, action.steps);
+        proptest::prop_assert_eq!(parse_action(&text), Ok(("", action)));
+    }
+
+    #[test]
+    fn test_parse_anything(input in "[URDL] -?[0-9]{1,12}|.*") {
+        let _ = parse_action(&input);
+    }
 }
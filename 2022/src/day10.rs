@@ -1,23 +1,40 @@
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use aoc_common::error::AocError;
+use aoc_common::ocr;
+use aoc_common::parsers::lines;
+use aoc_common::simulation::{run_to_end, Simulation};
+use aoc_common::solution::{Answer, Solution};
+use log::{debug, trace};
 use nom::branch::alt;
-use nom::IResult;
 use nom::bytes::complete::tag;
-use nom::sequence::preceded;
 use nom::character::complete::i32;
+use nom::sequence::preceded;
+use nom::IResult;
 
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        lines(input, parse_instruction)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
+
+    // Both parts run the same program.
+    fn simulation(input: &Self::Input, _part: u32) -> Result<Box<dyn Simulation + '_>, AocError> {
+        Ok(Box::new(Program::new(input)?))
+    }
 }
 
-enum Instruction {
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instruction {
     Addx(i32),
     Noop,
 }
@@ -36,7 +53,6 @@ fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
     alt((parse_addx, parse_noop))(input)
 }
 
-
 struct Machine {
     cycle_number: u32,
     current_instruction: Option<Instruction>,
@@ -62,7 +78,8 @@ impl Machine {
     fn feed(&mut self, i: Instruction) {
         self.current_instruction = Some(i);
 
-        self.current_instruction_remaining_cycles = match self.current_instruction.as_ref().unwrap() {
+        self.current_instruction_remaining_cycles = match self.current_instruction.as_ref().unwrap()
+        {
             Instruction::Addx(_) => 2,
             Instruction::Noop => 1,
         };
@@ -77,118 +94,148 @@ impl Machine {
             0 => {
                 match self.current_instruction.as_ref().unwrap() {
                     Instruction::Addx(num) => self.reg_x += num,
-                    Instruction::Noop => {},
+                    Instruction::Noop => {}
                 }
 
                 self.current_instruction = None;
 
                 State::Done
-            },
+            }
             _ => State::InProgress,
         }
     }
 }
 
-fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<i32, Box<dyn Error>> {
-    let mut m = Machine::new();
-    let (_, instr) = parse_instruction(&input.next().unwrap().unwrap()).or(Err("could not parse instruction"))?;
-    m.feed(instr);
-    let mut stop = false;
-
-    let mut total_signal_strength: i32 = 0;
-
-    loop {
-        if stop {
-            break;
-        }
-
-        match m.tick() {
-            State::Done => {
-                if let Some(line) = input.next() {
-                    let line = line?;
-                    let (_, instr) = parse_instruction(&line).or(Err("could not parse instruction"))?;
-
-                    m.feed(instr);
-                } else {
-                    stop = true;
-                }
-            }
-            State::InProgress => {
-            }
-        }
+// Runs the program one cycle per step, adding up the signal strength (part 1) and drawing the
+// CRT (part 2) along the way.
+struct Program<'a> {
+    machine: Machine,
+    instructions: std::slice::Iter<'a, Instruction>,
+    stopped: bool,
+    total_signal_strength: i32,
+    crt_line: String,
+    screen: String,
+}
 
-        if m.cycle_number >= 20 {
-            if (m.cycle_number - 20) % 40 == 0 {
-                let signal = m.reg_x * i32::try_from(m.cycle_number).unwrap();
-                println!("during {} cycle: reg_x = {}, score = {}", m.cycle_number, m.reg_x, signal);
-                total_signal_strength += signal;
-            }
-        }
+impl<'a> Program<'a> {
+    fn new(input: &'a [Instruction]) -> Result<Program<'a>, AocError> {
+        let mut instructions = input.iter();
+        let mut machine = Machine::new();
+        machine.feed(instructions.next().ok_or("empty program")?.clone());
+
+        Ok(Program {
+            machine,
+            instructions,
+            stopped: false,
+            total_signal_strength: 0,
+            crt_line: String::with_capacity(40),
+            screen: String::new(),
+        })
     }
-
-    Ok(total_signal_strength)
 }
 
-fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<(), Box<dyn Error>> {
-    let mut m = Machine::new();
-    let (_, instr) = parse_instruction(&input.next().unwrap().unwrap()).or(Err("could not parse instruction"))?;
-    m.feed(instr);
-    let mut stop = false;
-
-    let mut crt_line = String::with_capacity(40);
-
-    loop {
-        if stop {
-            break;
+impl Simulation for Program<'_> {
+    fn step(&mut self) -> bool {
+        if self.stopped {
+            return false;
         }
 
+        let m = &mut self.machine;
         let crt_position: i32 = i32::try_from((m.cycle_number - 1) % 40).unwrap();
 
         if crt_position >= m.reg_x - 1 && crt_position <= m.reg_x + 1 {
-            crt_line.push('#');
+            self.crt_line.push('#');
         } else {
-            crt_line.push('.');
+            self.crt_line.push('.');
         }
 
         if crt_position % 40 == 39 {
-            println!("{}", crt_line);
-            crt_line.clear();
+            trace!("{}", self.crt_line);
+            self.screen.push_str(&self.crt_line);
+            self.screen.push('\n');
+            self.crt_line.clear();
         }
 
         match m.tick() {
             State::Done => {
-                if let Some(line) = input.next() {
-                    let line = line?;
-                    let (_, instr) = parse_instruction(&line).or(Err("could not parse instruction"))?;
-
-                    m.feed(instr);
+                if let Some(instr) = self.instructions.next() {
+                    m.feed(instr.clone());
                 } else {
-                    stop = true;
+                    self.stopped = true;
                 }
             }
-            State::InProgress => {
-            }
+            State::InProgress => {}
         }
+
+        if m.cycle_number >= 20 && (m.cycle_number - 20).is_multiple_of(40) {
+            let signal = m.reg_x * i32::try_from(m.cycle_number).unwrap();
+            debug!(
+                "during {} cycle: reg_x = {}, score = {}",
+                m.cycle_number, m.reg_x, signal
+            );
+            self.total_signal_strength += signal;
+        }
+
+        true
     }
 
-    Ok(())
+    fn render(&self) -> String {
+        let m = &self.machine;
+        let instruction = match &m.current_instruction {
+            Some(Instruction::Addx(num)) => format!("addx {}", num),
+            Some(Instruction::Noop) => "noop".to_string(),
+            None => "-".to_string(),
+        };
+
+        format!(
+            "cycle {}, X = {}, executing {}, signal strength {}\n\n{}{}\n",
+            m.cycle_number,
+            m.reg_x,
+            instruction,
+            self.total_signal_strength,
+            self.screen,
+            self.crt_line
+        )
+    }
+}
+
+fn part1(input: &[Instruction]) -> Result<i32, AocError> {
+    let mut program = Program::new(input)?;
+    run_to_end(&mut program);
+
+    Ok(program.total_signal_strength)
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2022!");
+fn part2(input: &[Instruction]) -> Result<String, AocError> {
+    let mut program = Program::new(input)?;
+    run_to_end(&mut program);
 
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
+    // The example draws a pattern rather than letters, keep the picture for that.
+    Ok(ocr::read_letters(&program.screen).unwrap_or(program.screen))
+}
 
-    // TODO: Refactor how to find the file.
-    // let lines = read_input("src/input/day10_example")?;
-    let lines = read_input("src/input/day10")?;
-    println!("Part 1: {}", part1(lines)?);
+#[test]
+fn test_parse_trailing() {
+    assert!(matches!(
+        Day10::parse("noop\naddx 5 junk\n"),
+        Err(AocError::Parse(e)) if (e.line, e.column) == (2, 7)
+    ));
+}
 
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day10")?;
-    println!("Part 2:");
-    part2(lines)?;
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parse_roundtrip(value: i32, noop: bool) {
+        let (text, instruction) = if noop {
+            ("noop".to_string(), Instruction::Noop)
+        } else {
+            (format!("addx {}", value), Instruction::Addx(value))
+        };
+        proptest::prop_assert_eq!(parse_instruction(&text), Ok(("", instruction)));
+    }
 
-    Ok(())
+    #[test]
+    fn test_parse_anything(input in "addx -?[0-9]{1,12}|.*") {
+        let _ = parse_instruction(&input);
+    }
 }
@@ -1,30 +1,38 @@
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::Read;
-use std::io;
-use std::path::Path;
-
+use aoc_common::error::AocError;
+use aoc_common::parsers::{groups, unsigned};
+use aoc_common::random::Rng;
+use aoc_common::solution::{Answer, Solution};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::line_ending;
 use nom::character::complete::u64;
-use nom::combinator::opt;
-use nom::multi::many1;
+use nom::combinator::map;
 use nom::multi::separated_list1;
 use nom::sequence::tuple;
 use nom::sequence::{preceded, terminated};
 use nom::IResult;
+use std::num::NonZeroU64;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        groups(input, parse_monkey)
+    }
 
-fn read_input<P>(filename: P) -> io::Result<String>
-where
-    P: AsRef<Path>,
-{
-    let mut file = File::open(filename)?;
-    let mut buf = String::new();
-    file.read_to_string(&mut buf)?;
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
 
-    Ok(buf)
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, AocError> {
+        Ok(generate(rng, size))
+    }
 }
 
 fn monkey_id(input: &str) -> IResult<&str, u64> {
@@ -62,27 +70,31 @@ fn monkey_operation(input: &str) -> IResult<&str, Operation> {
     )(input)
 }
 
+// Divisible by 0 would make no sense, part 2 even divides by it.
 fn monkey_test(input: &str) -> IResult<&str, u64> {
-    preceded(tag("  Test: divisible by "), u64)(input)
+    map(
+        preceded(tag("  Test: divisible by "), unsigned::<NonZeroU64>),
+        NonZeroU64::get,
+    )(input)
 }
 
-fn monkey_test_cond_true_target(input: &str) -> IResult<&str, u64> {
-    preceded(tag("    If true: throw to monkey "), u64)(input)
+fn monkey_test_cond_true_target(input: &str) -> IResult<&str, usize> {
+    preceded(tag("    If true: throw to monkey "), unsigned)(input)
 }
 
-fn monkey_test_cond_false_target(input: &str) -> IResult<&str, u64> {
-    preceded(tag("    If false: throw to monkey "), u64)(input)
+fn monkey_test_cond_false_target(input: &str) -> IResult<&str, usize> {
+    preceded(tag("    If false: throw to monkey "), unsigned)(input)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 enum Operation {
     Multiply(u64),
     Add(u64),
     Square,
 }
 
-#[derive(Debug, Clone)]
-struct Monkey {
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monkey {
     items: Vec<u64>,
     operation: Operation,
     test: u64,
@@ -92,14 +104,13 @@ struct Monkey {
 }
 
 fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
-    let (input, (_id, starting_items, op, test, test_true, test_false, _)) = tuple((
+    let (input, (_id, starting_items, op, test, test_true, test_false)) = tuple((
         terminated(monkey_id, line_ending),
         terminated(monkey_starting_items, line_ending),
         terminated(monkey_operation, line_ending),
         terminated(monkey_test, line_ending),
         terminated(monkey_test_cond_true_target, line_ending),
-        terminated(monkey_test_cond_false_target, opt(line_ending)),
-        opt(line_ending),
+        monkey_test_cond_false_target,
     ))(input)?;
 
     Ok((
@@ -107,81 +118,226 @@ fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
         Monkey {
             items: starting_items,
             operation: op,
-            test: test,
-            test_cond_true_target: usize::try_from(test_true).unwrap(),
-            test_cond_false_target: usize::try_from(test_false).unwrap(),
-            inspections: 0
+            test,
+            test_cond_true_target: test_true,
+            test_cond_false_target: test_false,
+            inspections: 0,
         },
     ))
 }
 
-fn parse_monkeys(input: &str) -> IResult<&str, Vec<Monkey>> {
-    many1(parse_monkey)(input)
-}
-
-fn run_round(monkeys: &mut Vec<Monkey>, post_op: impl Fn(u64) -> u64) {
+fn run_round(monkeys: &mut [Monkey], post_op: impl Fn(u64) -> u64) -> Result<(), AocError> {
     for monkey_id in 0..monkeys.len() {
         while let Some(item) = monkeys[monkey_id].items.pop() {
             monkeys[monkey_id].inspections += 1;
 
-            let new_worry_level = post_op(match monkeys[monkey_id].operation {
-                Operation::Multiply(num) => item * num,
-                Operation::Add(num) => item + num,
-                Operation::Square => item * item,
-            });
+            let new_worry_level = match monkeys[monkey_id].operation {
+                Operation::Multiply(num) => item.checked_mul(num),
+                Operation::Add(num) => item.checked_add(num),
+                Operation::Square => item.checked_mul(item),
+            };
+            let new_worry_level = post_op(new_worry_level.ok_or("worry level overflowed")?);
 
-            let target_monkey_id = if new_worry_level % monkeys[monkey_id].test == 0 {
+            let target_monkey_id = if new_worry_level.is_multiple_of(monkeys[monkey_id].test) {
                 monkeys[monkey_id].test_cond_true_target
             } else {
                 monkeys[monkey_id].test_cond_false_target
             };
-            monkeys[target_monkey_id].items.push(new_worry_level);
+            // It would keep inspecting the same item forever.
+            if target_monkey_id == monkey_id {
+                return Err(format!("monkey {} throws to itself", monkey_id).into());
+            }
+            monkeys
+                .get_mut(target_monkey_id)
+                .ok_or("thrown to a monkey that doesn't exist")?
+                .items
+                .push(new_worry_level);
         }
     }
+
+    Ok(())
 }
 
-fn run(mut monkeys: Vec<Monkey>, num_rounds: u64, post_op: impl Fn(u64) -> u64)-> Result<u64, Box<dyn Error>> {
+fn run(
+    mut monkeys: Vec<Monkey>,
+    num_rounds: u64,
+    post_op: impl Fn(u64) -> u64,
+) -> Result<u64, AocError> {
     for _ in 0..num_rounds {
-        run_round(&mut monkeys, &post_op);
+        run_round(&mut monkeys, &post_op)?;
     }
-    
-    let mut monkeys_inspections: Vec<u64> = monkeys.iter().map(|monkey| monkey.inspections).collect();
+
+    let mut monkeys_inspections: Vec<u64> =
+        monkeys.iter().map(|monkey| monkey.inspections).collect();
     monkeys_inspections.sort();
 
-    Ok(monkeys_inspections[monkeys_inspections.len() - 1] * monkeys_inspections[monkeys_inspections.len() - 2])
+    let [.., second, first] = monkeys_inspections[..] else {
+        return Err("monkey business needs at least two monkeys".into());
+    };
+    Ok(first
+        .checked_mul(second)
+        .ok_or("monkey business overflowed")?)
+}
+
+// `monkey` the way the puzzle input describes it.
+fn write_monkey(id: usize, monkey: &Monkey) -> String {
+    let items: Vec<String> = monkey.items.iter().map(u64::to_string).collect();
+    let operation = match monkey.operation {
+        Operation::Multiply(num) => format!("* {}", num),
+        Operation::Add(num) => format!("+ {}", num),
+        Operation::Square => "* old".to_string(),
+    };
+    format!(
+        "Monkey {}:\n  Starting items: {}\n  Operation: new = old {}\n  \
+         Test: divisible by {}\n    If true: throw to monkey {}\n    \
+         If false: throw to monkey {}\n",
+        id,
+        items.join(", "),
+        operation,
+        monkey.test,
+        monkey.test_cond_true_target,
+        monkey.test_cond_false_target
+    )
+}
+
+// Like the real inputs: `size` monkeys (2 to 8) testing for distinct primes, one of them squaring
+// the worry level. Inputs whose worry levels overflow in part 1 are thrown away. Few monkeys
+// passing items back and forth overflow easily, so after a few tries there is no squaring and
+// then no multiplying any more.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(2, 8);
+
+    for attempt in 0.. {
+        let mut primes = vec![2, 3, 5, 7, 11, 13, 17, 19, 23];
+        rng.shuffle(&mut primes);
+        let squaring = if attempt < 10 {
+            rng.index(count)
+        } else {
+            count
+        };
+        let multiplying = if attempt < 20 { 0.5 } else { 0.0 };
+
+        let monkeys: Vec<Monkey> = (0..count)
+            .map(|id| Monkey {
+                items: (0..rng.range(1..=6)).map(|_| rng.range(50..=99)).collect(),
+                operation: if id == squaring {
+                    Operation::Square
+                } else if rng.chance(multiplying) {
+                    Operation::Multiply(rng.range(2..=19))
+                } else {
+                    Operation::Add(rng.range(1..=8))
+                },
+                test: primes[id],
+                // Monkeys never throw to themselves.
+                test_cond_true_target: (id + 1 + rng.index(count - 1)) % count,
+                test_cond_false_target: (id + 1 + rng.index(count - 1)) % count,
+                inspections: 0,
+            })
+            .collect();
+
+        if part1(&monkeys).is_err() {
+            continue;
+        }
+
+        let monkeys: Vec<String> = monkeys
+            .iter()
+            .enumerate()
+            .map(|(id, monkey)| write_monkey(id, monkey))
+            .collect();
+
+        return monkeys.join("\n");
+    }
+
+    unreachable!("adding alone can't overflow")
 }
 
-fn part1(input: &str) -> Result<u64, Box<dyn Error>> {
-    let (_, monkeys) = parse_monkeys(&input).map_err(|e| e.to_string())?;
+fn part1(monkeys: &[Monkey]) -> Result<u64, AocError> {
+    run(monkeys.to_vec(), 20, |x| x / 3)
+}
 
-    run(monkeys, 20, |x| x / 3)
+// Part 2
+
+fn part2(monkeys: &[Monkey]) -> Result<u64, AocError> {
+    let mod_product = monkeys
+        .iter()
+        .try_fold(1u64, |product, monkey| product.checked_mul(monkey.test))
+        .ok_or("the product of the divisors overflowed")?;
+
+    run(monkeys.to_vec(), 10_000, |x| x % mod_product)
 }
 
-/// Part 2
+#[test]
+fn test_invalid_monkeys() {
+    let monkey = |test, target| Monkey {
+        items: vec![79],
+        operation: Operation::Add(1),
+        test,
+        test_cond_true_target: target,
+        test_cond_false_target: target,
+        inspections: 0,
+    };
+
+    let zero = write_monkey(0, &monkey(0, 1));
+    assert!(matches!(
+        Day11::parse(&zero),
+        Err(AocError::Parse(e)) if (e.line, e.column) == (4, 22)
+    ));
 
-fn part2(input: &str) -> Result<u64, Box<dyn Error>> {
-    let (_, monkeys) = parse_monkeys(&input).map_err(|e| e.to_string())?;
-    
-    let mod_product = monkeys.iter().map(|monkey| monkey.test).fold(1, |acc, d| acc * d);
-    
-    run(monkeys, 10_000, |x| x % mod_product)
+    assert!(part1(&[monkey(2, 1)]).is_err());
+    assert!(part1(&[monkey(2, 0), monkey(3, 1)]).is_err());
+    assert!(part2(&[monkey(u64::MAX, 1), monkey(2, 0)]).is_err());
+    assert!(part1(&[monkey(2, 1), monkey(3, 0)]).is_ok());
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2022!");
+#[cfg(test)]
+fn monkeys() -> impl proptest::strategy::Strategy<Value = Vec<Monkey>> {
+    use proptest::prelude::*;
 
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
+    let operation = prop_oneof![
+        any::<u64>().prop_map(Operation::Multiply),
+        any::<u64>().prop_map(Operation::Add),
+        Just(Operation::Square),
+    ];
+    let monkey = (
+        prop::collection::vec(any::<u64>(), 1..5),
+        operation,
+        1..=u64::MAX,
+        any::<usize>(),
+        any::<usize>(),
+    )
+        .prop_map(|(items, operation, test, if_true, if_false)| Monkey {
+            items,
+            operation,
+            test,
+            test_cond_true_target: if_true,
+            test_cond_false_target: if_false,
+            inspections: 0,
+        });
 
-    // TODO: Refactor how to find the file.
-    // let input = read_input("src/input/day11_example")?;
-    let input = read_input("src/input/day11")?;
-    println!("Part 1: {}", part1(&input)?);
+    prop::collection::vec(monkey, 1..5)
+}
 
-    // TODO: Refactor how to find the file.
-    // let input = read_input("src/input/day11_example")?;
-    let input = read_input("src/input/day11")?;
-    println!("Part 2: {}", part2(&input)?);
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parse_monkey_roundtrip(monkeys in monkeys()) {
+        let text: Vec<String> = monkeys
+            .iter()
+            .enumerate()
+            .map(|(id, monkey)| write_monkey(id, monkey))
+            .collect();
+        proptest::prop_assert_eq!(groups(&text.join("\n"), parse_monkey).unwrap(), monkeys);
+    }
 
-    Ok(())
+    #[test]
+    fn test_parse_monkey_anything(input: String) {
+        let _ = parse_monkey(&input);
+        let _ = groups(&input, parse_monkey);
+    }
+
+    #[test]
+    fn test_parse_monkey_truncated(monkeys in monkeys(), cut: proptest::sample::Index) {
+        let text = write_monkey(0, &monkeys[0]);
+        let _ = parse_monkey(&text[..cut.index(text.len())]);
+    }
 }
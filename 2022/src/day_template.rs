@@ -1,50 +1,30 @@
-use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::anychar;
-use nom::combinator::{map, opt};
-use nom::sequence::{delimited, terminated};
-use nom::IResult;
-use regex::Regex;
-
-fn read_input<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+use aoc_common::error::AocError;
+use aoc_common::solution::{Answer, Solution};
 
-fn part1(mut input: io::Lines<io::BufReader<File>>) -> Result<String, Box<dyn Error>> {
-    todo!()
-}
+pub struct DayNN;
 
-/// Part 2
+impl Solution for DayNN {
+    type Input = Vec<String>;
 
-fn part2(mut input: io::Lines<io::BufReader<File>>) -> Result<String, Box<dyn Error>> {
-    todo!()
-}
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(input.lines().map(String::from).collect())
+    }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    println!("Hello AOC 2022!");
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part1(input)?.into())
+    }
 
-    let path = env::current_dir()?;
-    println!("The current directory is {}", path.display());
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(part2(input)?.into())
+    }
+}
 
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day06_example")?;
-    // let lines = read_input("src/input/day06")?;
-    println!("Part 1: {}", part1(lines)?);
+fn part1(_input: &[String]) -> Result<String, AocError> {
+    todo!()
+}
 
-    // TODO: Refactor how to find the file.
-    let lines = read_input("src/input/day06_example")?;
-    // let lines = read_input("src/input/day06")?;
-    println!("Part 2: {}", part2(lines)?);
+// Part 2
 
-    Ok(())
+fn part2(_input: &[String]) -> Result<String, AocError> {
+    todo!()
 }
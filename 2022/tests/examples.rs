@@ -0,0 +1,5 @@
+// One test per example input and part, generated by build.rs from src/input.
+
+use aoc2022::YEAR;
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
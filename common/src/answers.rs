@@ -0,0 +1,92 @@
+use crate::input::{read_string, InputError};
+use crate::solution::Answer;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Accepted answers, keyed by input file name and part.
+///
+/// The file has one answer per line: `<input file> <part> <answer>`, e.g. `day07_example 2 168`.
+/// Blank lines and lines starting with `#` are ignored. Answers spanning several lines (pictures)
+/// are written on one line with `\n` between the rows.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(String, u32), String>,
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Answers, InputError> {
+        let mut answers = HashMap::new();
+
+        for (idx, line) in input.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let malformed = || InputError::MalformedAnswer {
+                line: idx + 1,
+                text: line.to_string(),
+            };
+
+            let mut fields = line.splitn(3, ' ');
+            let name = fields.next().ok_or_else(malformed)?;
+            let part = fields
+                .next()
+                .and_then(|part| part.parse::<u32>().ok())
+                .ok_or_else(malformed)?;
+            let answer = fields.next().ok_or_else(malformed)?;
+
+            answers.insert((name.to_string(), part), answer.to_string());
+        }
+
+        Ok(Answers { answers })
+    }
+
+    /// The accepted answer for `part` of the input file `name`, as written in the file.
+    pub fn get(&self, name: &str, part: u32) -> Option<&str> {
+        self.answers
+            .get(&(name.to_string(), part))
+            .map(String::as_str)
+    }
+
+    /// Whether `answer` matches the accepted answer, or `None` if there is none on record.
+    pub fn check(&self, name: &str, part: u32, answer: &Answer) -> Option<bool> {
+        self.get(name, part)
+            .map(|expected| expected == escape(answer))
+    }
+}
+
+/// Writes `answer` the way it appears in an answers file.
+pub fn escape(answer: &Answer) -> String {
+    answer.to_string().trim_end().replace('\n', "\\n")
+}
+
+/// Reads the answers file at `path`.
+pub fn read_answers<P>(path: P) -> Result<Answers, InputError>
+where
+    P: AsRef<Path>,
+{
+    Answers::parse(&read_string(path)?)
+}
+
+#[test]
+fn test_answers() {
+    let answers = Answers::parse("# comment\n\nday01 1 1448\nday13 2 #.#\\n.#.\n").unwrap();
+
+    assert_eq!(answers.get("day01", 1), Some("1448"));
+    assert_eq!(answers.get("day01", 2), None);
+    assert_eq!(
+        answers.check("day01", 1, &Answer::Unsigned(1448)),
+        Some(true)
+    );
+    assert_eq!(answers.check("day01", 1, &Answer::Signed(-1)), Some(false));
+    assert_eq!(
+        answers.check("day13", 2, &Answer::Text("#.#\n.#.\n".into())),
+        Some(true)
+    );
+    assert_eq!(answers.check("day02", 1, &Answer::Unsigned(1)), None);
+
+    assert!(matches!(
+        Answers::parse("day01 one 1448"),
+        Err(InputError::MalformedAnswer { line: 1, .. })
+    ));
+}
@@ -0,0 +1,108 @@
+// Graphs and trees in the DOT language of Graphviz, for looking at puzzle structures with
+// `dot -Tsvg`.
+
+use std::fmt;
+
+/// A graph to be written as DOT. Nodes are referred to by ids chosen by the caller, anything
+/// that can be displayed.
+pub struct Dot {
+    directed: bool,
+    // Already formatted statements, in the order they were added.
+    statements: Vec<String>,
+}
+
+// Attribute values are always quoted, so only quotes, backslashes and newlines need escaping.
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn attributes(attrs: &[(&str, &str)]) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+
+    let attrs: Vec<String> = attrs
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, quote(value)))
+        .collect();
+    format!(" [{}]", attrs.join(", "))
+}
+
+impl Dot {
+    /// A graph with edges in both directions, like a cave system.
+    pub fn graph() -> Dot {
+        Dot {
+            directed: false,
+            statements: Vec::new(),
+        }
+    }
+
+    /// A graph with edges from one node to another, like a tree.
+    pub fn digraph() -> Dot {
+        Dot {
+            directed: true,
+            statements: Vec::new(),
+        }
+    }
+
+    /// Adds a node with attributes, e.g. `("label", "start")` or `("shape", "box")`.
+    pub fn node(&mut self, id: impl fmt::Display, attrs: &[(&str, &str)]) {
+        self.statements
+            .push(format!("{}{}", quote(&id.to_string()), attributes(attrs)));
+    }
+
+    /// Adds an edge. Nodes without attributes don't have to be added first.
+    pub fn edge(&mut self, from: impl fmt::Display, to: impl fmt::Display, attrs: &[(&str, &str)]) {
+        let arrow = if self.directed { "->" } else { "--" };
+        self.statements.push(format!(
+            "{} {} {}{}",
+            quote(&from.to_string()),
+            arrow,
+            quote(&to.to_string()),
+            attributes(attrs)
+        ));
+    }
+}
+
+impl fmt::Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.directed { "digraph" } else { "graph" };
+        writeln!(f, "{} {{", kind)?;
+        for statement in &self.statements {
+            writeln!(f, "    {};", statement)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[test]
+fn test_dot() {
+    let mut dot = Dot::digraph();
+    dot.node(1, &[("label", "say \"hi\"\\\nbye"), ("shape", "box")]);
+    dot.node("b", &[]);
+    dot.edge(1, "b", &[("label", "x")]);
+
+    assert_eq!(
+        dot.to_string(),
+        "digraph {\n\
+        \x20   \"1\" [label=\"say \\\"hi\\\"\\\\\\nbye\", shape=\"box\"];\n\
+        \x20   \"b\";\n\
+        \x20   \"1\" -> \"b\" [label=\"x\"];\n\
+        }\n"
+    );
+
+    let mut dot = Dot::graph();
+    dot.edge("start", "A", &[]);
+    assert_eq!(dot.to_string(), "graph {\n    \"start\" -- \"A\";\n}\n");
+}
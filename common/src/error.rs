@@ -0,0 +1,193 @@
+use crate::input::InputError;
+use nom::error::ErrorKind;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Where in the puzzle input parsing broke.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Set by whoever read the input from disk, parsers only see the text.
+    pub file: Option<PathBuf>,
+    /// 1-based, like an editor shows it.
+    pub line: usize,
+    /// 1-based, counted in characters.
+    pub column: usize,
+    /// The whole offending line.
+    pub text: String,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub enum AocError {
+    Input(InputError),
+    Parse(ParseError),
+    /// The input parsed fine but the puzzle could not be solved with it.
+    Solve(String),
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    /// A parse error at `position`, which has to be a subslice of `input` (e.g. a line from
+    /// `input.lines()` or the remaining input returned by a nom parser).
+    pub fn at(input: &str, position: &str, message: impl Into<String>) -> AocError {
+        let offset = (position.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len());
+        debug_assert!(offset.is_some(), "position is not part of the input");
+        let offset = offset.unwrap_or(0);
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        AocError::Parse(ParseError {
+            file: None,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            message: message.into(),
+        })
+    }
+
+    /// Converts the error of a nom parser that was run on (a subslice of) `input`.
+    pub fn nom(input: &str, err: nom::Err<nom::error::Error<&str>>) -> AocError {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let message = match e.code {
+                    ErrorKind::Digit => "expected a number".to_string(),
+                    ErrorKind::Tag | ErrorKind::Char => "unexpected text".to_string(),
+                    ErrorKind::Eof => "unexpected trailing text".to_string(),
+                    ErrorKind::MapRes => "invalid value".to_string(),
+                    code => format!("{:?} failed", code),
+                };
+                AocError::at(input, e.input, message)
+            }
+            nom::Err::Incomplete(_) => {
+                AocError::at(input, &input[input.len()..], "unexpected end of input")
+            }
+        }
+    }
+
+    /// Records which file the input came from.
+    pub fn in_file(self, path: &Path) -> AocError {
+        match self {
+            AocError::Parse(e) => AocError::Parse(ParseError {
+                file: Some(path.to_path_buf()),
+                ..e
+            }),
+            e => e,
+        }
+    }
+}
+
+/// Parses `field`, a subslice of `input`, reporting its location if it is malformed.
+pub fn parse_at<T>(input: &str, field: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    field
+        .parse()
+        .map_err(|e| AocError::at(input, field, format!("invalid value {:?}: {}", field, e)))
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        writeln!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+
+        let gutter = self.line.to_string().len();
+        writeln!(f, "{:>gutter$} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{:>gutter$} | {:>column$}",
+            "",
+            "^",
+            column = self.column
+        )
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Input(e) => write!(f, "{}", e),
+            AocError::Parse(e) => write!(f, "{}", e),
+            AocError::Solve(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Input(e) => Some(e),
+            AocError::Parse(_) | AocError::Solve(_) => None,
+        }
+    }
+}
+
+impl From<InputError> for AocError {
+    fn from(e: InputError) -> Self {
+        AocError::Input(e)
+    }
+}
+
+impl From<&str> for AocError {
+    fn from(message: &str) -> Self {
+        AocError::Solve(message.to_string())
+    }
+}
+
+impl From<String> for AocError {
+    fn from(message: String) -> Self {
+        AocError::Solve(message)
+    }
+}
+
+#[test]
+fn test_at() {
+    let input = "1,2\n3,x\n";
+    let line = input.lines().nth(1).unwrap();
+
+    let AocError::Parse(e) = AocError::at(input, &line[2..], "not a number") else {
+        panic!("expected a parse error");
+    };
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 3, "3,x"));
+
+    let e = AocError::at(input, &line[2..], "not a number").in_file(Path::new("day01"));
+    assert_eq!(e.to_string(), "day01:2:3: not a number\n2 | 3,x\n  |   ^");
+}
+
+#[test]
+fn test_nom() {
+    use nom::character::complete::u32;
+
+    let input = "12\nab\n";
+    let line = input.lines().nth(1).unwrap();
+    let err = u32::<_, nom::error::Error<&str>>(line).unwrap_err();
+
+    let AocError::Parse(e) = AocError::nom(input, err) else {
+        panic!("expected a parse error");
+    };
+    assert_eq!((e.line, e.column), (2, 1));
+    assert_eq!(e.message, "expected a number");
+}
+
+#[test]
+fn test_parse_at() {
+    let input = "7 -> 8y";
+    let n: u32 = parse_at(input, &input[..1]).unwrap();
+    assert_eq!(n, 7);
+
+    let err = parse_at::<u32>(input, &input[5..]).unwrap_err();
+    assert!(matches!(err, AocError::Parse(ParseError { column: 6, .. })));
+}
@@ -0,0 +1,80 @@
+use crate::answers::{escape, read_answers};
+use crate::input::{example_paths, read_string};
+use crate::solution::{Year, STACK_SIZE};
+use std::fmt::Write;
+use std::path::Path;
+use std::{fs, panic, thread};
+
+/// Writes one `#[test]` per example input and part found in `input_dir` to `out`. Meant to be
+/// called from a build script, the result is `include!`d by the year's integration tests, which
+/// must have the year's `YEAR` in scope.
+pub fn generate_tests(input_dir: &Path, out: &Path) {
+    let answers = read_answers(input_dir.join("answers")).ok();
+    let mut tests = String::new();
+
+    for day in 1..=25 {
+        for (_, path) in example_paths(input_dir, day) {
+            let name = path.file_name().unwrap().to_string_lossy();
+            let empty = fs::metadata(&path).map_or(true, |metadata| metadata.len() == 0);
+
+            for part in 1..=2 {
+                // A freshly scaffolded day has an empty example and no answers yet. Its tests are
+                // ignored until they are filled in, instead of failing, but the build warns about
+                // every one of them and `aoc verify` lists them as missing.
+                let reason = if empty {
+                    Some("empty example")
+                } else if answers.as_ref().and_then(|a| a.get(&name, part)).is_none() {
+                    Some("no answer recorded")
+                } else {
+                    None
+                };
+                let ignore = match reason {
+                    Some(reason) => {
+                        println!(
+                            "cargo:warning={} part {}: {}, test ignored",
+                            name, part, reason
+                        );
+                        format!("#[ignore = \"{}\"]\n", reason)
+                    }
+                    None => String::new(),
+                };
+
+                writeln!(
+                    tests,
+                    "#[test]\n{ignore}fn {name}_part{part}() {{\n    \
+                     aoc_common::examples::check(&YEAR, {day}, \"{name}\", {part});\n}}\n",
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    println!("cargo:rerun-if-changed={}", input_dir.display());
+    fs::write(out, tests).unwrap();
+}
+
+/// Solves `part` of the example input `name` and compares it to the accepted answer.
+pub fn check(year: &Year, day: u32, name: &str, part: u32) {
+    let dir = Path::new(year.input_dir);
+    let answers = read_answers(dir.join("answers")).unwrap();
+    let expected = answers
+        .get(name, part)
+        .unwrap_or_else(|| panic!("no answer recorded for {} part {}", name, part));
+
+    let puzzle = year
+        .puzzle(day)
+        .unwrap_or_else(|| panic!("day {} is not registered", day));
+    let input = read_string(dir.join(name)).unwrap();
+
+    // Test threads get the default stack size, solve on one that gets what the runner uses.
+    let answer = thread::scope(|scope| {
+        thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, || puzzle.solve(&input, part).unwrap())
+            .unwrap()
+            .join()
+            .unwrap_or_else(|payload| panic::resume_unwind(payload))
+    });
+
+    assert_eq!(escape(&answer), expected);
+}
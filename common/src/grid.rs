@@ -0,0 +1,298 @@
+use crate::error::AocError;
+use crate::input::InputError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A position in a [`Grid`]: row first, then column, the same order as `field[i][j]`.
+pub type Pos = (usize, usize);
+
+/// Up, left, right, down.
+pub const DIRS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// All eight neighbours in reading order.
+pub const DIRS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid stored row by row in one `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from nested rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, InputError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (row, line) in rows.into_iter().enumerate() {
+            if line.len() != width {
+                return Err(InputError::RaggedGrid {
+                    row,
+                    expected: width,
+                    found: line.len(),
+                });
+            }
+            cells.extend(line);
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a character map, one row per line, converting every character with `f`.
+    pub fn parse<F>(input: &str, mut f: F) -> Result<Grid<T>, AocError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for line in input.lines() {
+            let before = cells.len();
+
+            for (idx, c) in line.char_indices() {
+                let cell = f(c).ok_or_else(|| {
+                    AocError::at(input, &line[idx..], format!("unexpected character {:?}", c))
+                })?;
+                cells.push(cell);
+            }
+
+            let found = cells.len() - before;
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(AocError::at(
+                    input,
+                    line,
+                    format!("expected {} columns, found {}", expected, found),
+                ));
+            }
+
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// All positions in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells with their positions in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() panics on 0, an empty grid simply has no rows.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// The position one step from `pos` in direction `(d_row, d_col)`, if it is on the grid.
+    pub fn step(&self, (row, col): Pos, (d_row, d_col): (isize, isize)) -> Option<Pos> {
+        let pos = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Neighbours of `pos` that share an edge with it.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS4.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Neighbours of `pos` including the diagonal ones.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS8.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// The positions seen when walking from `pos` (exclusive) in direction `dir` until the edge.
+    pub fn ray(&self, pos: Pos, dir: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, dir), move |&pos| self.step(pos, dir))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .flat_map(|col| self.column(col).cloned())
+                .collect(),
+        }
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut rotated = self.transpose();
+        for row in rotated.cells.chunks_mut(self.height.max(1)) {
+            row.reverse();
+        }
+        rotated
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "{:?} is outside of the {}x{} grid",
+                pos, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", pos, width, height))
+    }
+}
+
+/// One line per row, with a newline after every row.
+impl<T> fmt::Display for Grid<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows().take(self.height) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_parse() {
+    let grid = Grid::parse("12\n34\n", |c| c.to_digit(10)).unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (2, 2));
+    assert_eq!(grid[(1, 0)], 3);
+    assert_eq!(grid.to_string(), "12\n34\n");
+
+    assert!(matches!(
+        Grid::parse("12\n3x\n", |c| c.to_digit(10)),
+        Err(AocError::Parse(e)) if (e.line, e.column) == (2, 2)
+    ));
+    assert!(Grid::parse("12\n3\n", |c| c.to_digit(10)).is_err());
+}
+
+#[test]
+fn test_neighbors() {
+    let grid = Grid::new(3, 2, 0);
+
+    assert_eq!(
+        grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+        vec![(0, 1), (1, 0)]
+    );
+    assert_eq!(grid.neighbors8((0, 1)).count(), 5);
+    assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+    assert_eq!(
+        grid.ray((1, 0), (0, 1)).collect::<Vec<_>>(),
+        vec![(1, 1), (1, 2)]
+    );
+    assert_eq!(grid.ray((0, 0), (-1, 0)).count(), 0);
+}
+
+#[test]
+fn test_transform() {
+    let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+    assert_eq!(grid.column(1).collect::<Vec<_>>(), vec![&2, &5]);
+    assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+    assert_eq!(grid.rotate().to_string(), "41\n52\n63\n");
+    assert_eq!(grid.map(|n| n * 2).row(1), &[8, 10, 12]);
+    assert!(Grid::from_rows(vec![vec![1], vec![]]).is_err());
+}
@@ -0,0 +1,201 @@
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::parsers::groups;
+use nom::IResult;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum InputError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    RaggedGrid {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    MalformedAnswer {
+        line: usize,
+        text: String,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Io { path, source } => {
+                write!(f, "could not read {}: {}", path.display(), source)
+            }
+            InputError::RaggedGrid {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "grid row {} has {} columns, expected {}",
+                row + 1,
+                found,
+                expected
+            ),
+            InputError::MalformedAnswer { line, text } => {
+                write!(f, "malformed answer on line {}: {:?}", line, text)
+            }
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io { source, .. } => Some(source),
+            InputError::RaggedGrid { .. } | InputError::MalformedAnswer { .. } => None,
+        }
+    }
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> InputError + '_ {
+    move |source| InputError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// Location of a day's input inside `dir`: `day07` for the real input, `day07_example` for the
+/// first example and `day07_example2`, `day07_example3`, ... for further examples.
+pub fn input_path(dir: &Path, day: u32, example: Option<u32>) -> PathBuf {
+    match example {
+        None => dir.join(format!("day{:02}", day)),
+        Some(1) => dir.join(format!("day{:02}_example", day)),
+        Some(n) => dir.join(format!("day{:02}_example{}", day, n)),
+    }
+}
+
+/// Example inputs present in `dir` for `day`, as (example number, path) pairs.
+pub fn example_paths(dir: &Path, day: u32) -> Vec<(u32, PathBuf)> {
+    (1..)
+        .map(|n| (n, input_path(dir, day, Some(n))))
+        .take_while(|(_, path)| path.exists())
+        .collect()
+}
+
+/// Reads all of `reader` into a string, e.g. stdin or an in-memory buffer. `name` stands in for
+/// the path in errors.
+pub fn read_from<R>(mut reader: R, name: &Path) -> Result<String, InputError>
+where
+    R: Read,
+{
+    let mut input = String::new();
+    reader.read_to_string(&mut input).map_err(io_error(name))?;
+    Ok(input)
+}
+
+/// Reads the whole of `path` into a string.
+pub fn read_string<P>(path: P) -> Result<String, InputError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(io_error(path))
+}
+
+/// Reads `path` as a list of lines, without their line endings.
+pub fn read_lines<P>(path: P) -> Result<Vec<String>, InputError>
+where
+    P: AsRef<Path>,
+{
+    Ok(read_string(path)?.lines().map(String::from).collect())
+}
+
+/// Reads `path` as groups of lines separated by blank lines, see [`groups`].
+pub fn read_groups<P>(path: P) -> Result<Vec<Vec<String>>, AocError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let group_lines = |group: &str| -> IResult<&str, Vec<String>> {
+        Ok(("", group.lines().map(String::from).collect()))
+    };
+    groups(&read_string(path)?, group_lines).map_err(|e| e.in_file(path))
+}
+
+/// Reads `path` as a grid of characters, all rows must have the same width. See [`Grid::parse`].
+pub fn read_char_grid<P>(path: P) -> Result<Grid<char>, AocError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    Grid::parse(&read_string(path)?, Some).map_err(|e| e.in_file(path))
+}
+
+#[test]
+fn test_missing_file() {
+    let err = read_string("does/not/exist").unwrap_err();
+    assert!(err.to_string().starts_with("could not read does/not/exist"));
+}
+
+#[test]
+fn test_read_from() {
+    assert_eq!(
+        read_from("1\n2\n".as_bytes(), Path::new("<stdin>")).unwrap(),
+        "1\n2\n"
+    );
+
+    let err = read_from(&[0xff, 0xfe][..], Path::new("<stdin>")).unwrap_err();
+    assert!(err.to_string().starts_with("could not read <stdin>"));
+}
+
+#[test]
+fn test_example_paths() {
+    let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for name in ["day09_example", "day09_example2", "day09_example4"] {
+        fs::write(dir.join(name), "").unwrap();
+    }
+
+    let examples = example_paths(&dir, 9);
+    assert_eq!(examples.len(), 2);
+    assert_eq!(examples[1], (2, dir.join("day09_example2")));
+    assert!(example_paths(&dir, 6).is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_read_shapes() {
+    let dir = std::env::temp_dir().join(format!("aoc-shapes-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("day01");
+
+    fs::write(&path, "#.\r\n.#\r\n\r\n##\r\n").unwrap();
+    assert_eq!(read_lines(&path).unwrap(), ["#.", ".#", "", "##"]);
+    assert_eq!(read_groups(&path).unwrap(), [vec!["#.", ".#"], vec!["##"]]);
+
+    fs::write(&path, "#.\n.#\n").unwrap();
+    assert_eq!(read_char_grid(&path).unwrap().to_string(), "#.\n.#\n");
+    fs::write(&path, "#.\n#\n").unwrap();
+    assert!(matches!(
+        read_char_grid(&path),
+        Err(AocError::Parse(e)) if e.file.as_deref() == Some(path.as_path())
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_input_path() {
+    let dir = Path::new("src/input");
+    assert_eq!(input_path(dir, 7, None), Path::new("src/input/day07"));
+    assert_eq!(
+        input_path(dir, 7, Some(1)),
+        Path::new("src/input/day07_example")
+    );
+    assert_eq!(
+        input_path(dir, 9, Some(2)),
+        Path::new("src/input/day09_example2")
+    );
+}
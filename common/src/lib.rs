@@ -0,0 +1,16 @@
+// Code shared between the yearly puzzle crates.
+
+pub mod answers;
+pub mod dot;
+pub mod error;
+pub mod examples;
+pub mod grid;
+pub mod input;
+pub mod ocr;
+pub mod parsers;
+pub mod random;
+pub mod search;
+pub mod simulation;
+pub mod solution;
+pub mod sparse;
+pub mod visualize;
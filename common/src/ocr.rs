@@ -0,0 +1,185 @@
+// Reads the capital letters some puzzles draw as their answer. Only the letters that have shown
+// up in puzzles so far are known, the fonts have no glyphs for the others.
+
+use crate::error::AocError;
+use crate::grid::Grid;
+
+// 4 pixels wide (plus one blank column between letters) and 6 high.
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#\n"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###.\n"),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##.\n"),
+    ('E', "####\n#...\n###.\n#...\n#...\n####\n"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#...\n"),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###\n"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#\n"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##.\n"),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#\n"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####\n"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##.\n"),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#...\n"),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#\n"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###.\n"),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##.\n"),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####\n"),
+];
+
+// 6 pixels wide (plus two blank columns between letters) and 10 high.
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.\n",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.\n",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######\n",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....\n",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#\n",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#\n",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..\n",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#\n",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######\n",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#\n",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....\n",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#\n",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#\n",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######\n",
+    ),
+];
+
+/// Reads the letters in `pixels`, where `true` is a lit pixel. Blank rows and columns around
+/// the letters are ignored, the font is picked by the height of what is left.
+pub fn recognize(pixels: &Grid<bool>) -> Result<String, AocError> {
+    let lit_rows: Vec<usize> = (0..pixels.height())
+        .filter(|&row| pixels.row(row).contains(&true))
+        .collect();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Err("no letters drawn".into());
+    };
+
+    let font = match bottom - top + 1 {
+        6 => SMALL,
+        10 => LARGE,
+        height => return Err(format!("no font is {} pixels high", height).into()),
+    };
+
+    let lit_column = |col| (top..=bottom).any(|row| pixels[(row, col)]);
+    let mut letters = String::new();
+    let mut col = 0;
+
+    // Letters are separated by at least one blank column, and have none inside.
+    while col < pixels.width() {
+        if !lit_column(col) {
+            col += 1;
+            continue;
+        }
+
+        let start = col;
+        while col < pixels.width() && lit_column(col) {
+            col += 1;
+        }
+
+        let mut glyph = String::new();
+        for row in top..=bottom {
+            for c in start..col {
+                glyph.push(if pixels[(row, c)] { '#' } else { '.' });
+            }
+            glyph.push('\n');
+        }
+
+        let (letter, _) = font
+            .iter()
+            .find(|(_, known)| *known == glyph)
+            .ok_or_else(|| format!("unknown letter:\n{}", glyph))?;
+        letters.push(*letter);
+    }
+
+    Ok(letters)
+}
+
+/// Reads the letters in a picture drawn with `#` for lit pixels and `.` for dark ones.
+pub fn read_letters(picture: &str) -> Result<String, AocError> {
+    recognize(&Grid::parse(picture, |c| Some(c == '#'))?)
+}
+
+#[cfg(test)]
+fn draw(letters: &str, font: &[(char, &str)], gap: &str) -> String {
+    let glyphs: Vec<Vec<&str>> = letters
+        .chars()
+        .map(|l| {
+            font.iter()
+                .find(|(c, _)| *c == l)
+                .unwrap()
+                .1
+                .lines()
+                .collect()
+        })
+        .collect();
+
+    (0..glyphs[0].len())
+        .map(|row| {
+            let row: Vec<&str> = glyphs.iter().map(|glyph| glyph[row]).collect();
+            row.join(gap) + gap + "\n"
+        })
+        .collect()
+}
+
+#[test]
+fn test_read_letters() {
+    let all: String = SMALL.iter().map(|(c, _)| c).collect();
+    assert_eq!(read_letters(&draw(&all, SMALL, ".")).unwrap(), all);
+
+    let all: String = LARGE.iter().map(|(c, _)| c).collect();
+    assert_eq!(read_letters(&draw(&all, LARGE, "..")).unwrap(), all);
+
+    // A blank line above doesn't matter, a square isn't a letter.
+    let hello = draw("HELLO", SMALL, ".");
+    let blank = ".".repeat(hello.find('\n').unwrap());
+    assert_eq!(
+        read_letters(&format!("{}\n{}", blank, hello)).unwrap(),
+        "HELLO"
+    );
+    assert!(read_letters("##\n##\n").is_err());
+    assert!(read_letters("....\n").is_err());
+}
@@ -0,0 +1,214 @@
+// nom parsers for the input shapes that keep coming back. The whole-input helpers at the bottom
+// insist on consuming everything they are given, so trailing garbage is reported instead of being
+// silently ignored.
+
+use crate::error::AocError;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, digit1, multispace0, space0};
+use nom::combinator::{all_consuming, map_res, opt, recognize};
+use nom::multi::separated_list1;
+use nom::sequence::{pair, preceded, separated_pair, terminated};
+use nom::IResult;
+use std::str::FromStr;
+
+/// A decimal number without sign. Numbers too large for `T` are an error.
+pub fn unsigned<T>(input: &str) -> IResult<&str, T>
+where
+    T: FromStr,
+{
+    map_res(digit1, str::parse)(input)
+}
+
+/// A decimal number with an optional `-` or `+` in front.
+pub fn signed<T>(input: &str) -> IResult<&str, T>
+where
+    T: FromStr,
+{
+    map_res(
+        recognize(pair(opt(alt((char('-'), char('+')))), digit1)),
+        str::parse,
+    )(input)
+}
+
+/// One or more items separated by commas, optionally followed by spaces: `3,4,3,1` or `79, 98`.
+pub fn list<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    separated_list1(pair(char(','), space0), item)
+}
+
+/// Two bounds separated by `separator`, like `2-4` or `20..30`.
+pub fn range<'a, O, F>(
+    separator: &'static str,
+    mut bound: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (O, O)>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    move |input| {
+        let (input, start) = bound(input)?;
+        let (input, _) = tag(separator)(input)?;
+        let (input, end) = bound(input)?;
+        Ok((input, (start, end)))
+    }
+}
+
+/// `key=value`, like the `x=20..30` in `target area: x=20..30, y=-10..-5`.
+pub fn key_value<'a, O, F>(
+    key: &'static str,
+    value: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    preceded(pair(tag(key), char('=')), value)
+}
+
+/// Two coordinates, see [`point`].
+pub type Point<T> = (T, T);
+
+/// A point `x,y`.
+pub fn point<T>(input: &str) -> IResult<&str, Point<T>>
+where
+    T: FromStr,
+{
+    separated_pair(signed, char(','), signed)(input)
+}
+
+/// A line segment `x1,y1 -> x2,y2`.
+pub fn segment<T>(input: &str) -> IResult<&str, (Point<T>, Point<T>)>
+where
+    T: FromStr,
+{
+    separated_pair(point, tag(" -> "), point)(input)
+}
+
+/// Runs `parser` on `part`, a subslice of `input`, which it must consume completely. Errors are
+/// located in `input`.
+pub fn parse_part<'a, O, F>(input: &str, part: &'a str, parser: F) -> Result<O, AocError>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    all_consuming(parser)(part)
+        .map(|(_, parsed)| parsed)
+        .map_err(|e| AocError::nom(input, e))
+}
+
+/// Runs `parser` on the whole of `input`, which may only be followed by whitespace.
+pub fn parse_all<'a, O, F>(input: &'a str, parser: F) -> Result<O, AocError>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    parse_part(input, input, terminated(parser, multispace0))
+}
+
+// `str::lines` leaves the `\r` on a last line that has no `\n` after it.
+fn without_cr(line: &str) -> &str {
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// Runs `parser` on every line of `input`, it must consume the whole line.
+pub fn lines<'a, O, F>(input: &'a str, mut parser: F) -> Result<Vec<O>, AocError>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    input
+        .lines()
+        .map(|line| parse_part(input, without_cr(line), &mut parser))
+        .collect()
+}
+
+/// Runs `parser` on every group of lines in `input`, groups are separated by blank lines. It
+/// must consume the whole group, which doesn't include the newline after its last line. Lines may
+/// end in `\n` or `\r\n`, the lines inside a group keep theirs.
+pub fn groups<'a, O, F>(input: &'a str, mut parser: F) -> Result<Vec<O>, AocError>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    let offset = |line: &str| line.as_ptr() as usize - input.as_ptr() as usize;
+    let mut groups = Vec::new();
+    // Byte range of the group collected so far.
+    let mut group: Option<(usize, usize)> = None;
+
+    for line in input.lines().map(without_cr) {
+        if line.is_empty() {
+            groups.extend(group.take());
+        } else {
+            let start = group.map_or(offset(line), |(start, _)| start);
+            group = Some((start, offset(line) + line.len()));
+        }
+    }
+    groups.extend(group);
+
+    groups
+        .into_iter()
+        .map(|(start, end)| parse_part(input, &input[start..end], &mut parser))
+        .collect()
+}
+
+#[test]
+fn test_numbers() {
+    assert_eq!(unsigned::<u8>("42,"), Ok((",", 42)));
+    assert!(unsigned::<u8>("256").is_err());
+    assert!(unsigned::<u32>("-1").is_err());
+    assert_eq!(signed::<i32>("-17..5"), Ok(("..5", -17)));
+    assert_eq!(signed::<i32>("+3"), Ok(("", 3)));
+    assert!(signed::<i32>("-").is_err());
+}
+
+#[test]
+fn test_shapes() {
+    assert_eq!(list(unsigned::<u32>)("3,4, 3"), Ok(("", vec![3, 4, 3])));
+    assert_eq!(list(unsigned::<u32>)("3,x"), Ok((",x", vec![3])));
+    assert_eq!(range("-", unsigned::<u32>)("2-4,6-8"), Ok((",6-8", (2, 4))));
+    assert_eq!(
+        key_value("y", range("..", signed::<i32>))("y=-10..-5"),
+        Ok(("", (-10, -5)))
+    );
+    assert_eq!(segment::<u32>("0,9 -> 5,9"), Ok(("", ((0, 9), (5, 9)))));
+}
+
+#[test]
+fn test_whole_input() {
+    assert_eq!(
+        parse_all("1,2,3\n", list(unsigned::<u32>)).unwrap(),
+        [1, 2, 3]
+    );
+    assert!(matches!(
+        parse_all("1,2,3x\n", list(unsigned::<u32>)),
+        Err(AocError::Parse(e)) if (e.line, e.column) == (1, 6)
+    ));
+
+    assert_eq!(lines("1\n2\n", unsigned::<u32>).unwrap(), [1, 2]);
+    assert!(matches!(
+        lines("1\n2 \n", unsigned::<u32>),
+        Err(AocError::Parse(e)) if (e.line, e.column) == (2, 2)
+    ));
+
+    let sums = groups("1\n2\n\n\n3\n", |group| {
+        let (rest, numbers) = separated_list1(char('\n'), unsigned::<u32>)(group)?;
+        Ok((rest, numbers.iter().sum::<u32>()))
+    });
+    assert_eq!(sums.unwrap(), [3, 3]);
+    assert!(matches!(
+        groups("1\n\n2\nx\n", unsigned::<u32>),
+        Err(AocError::Parse(e)) if (e.line, e.column) == (3, 2)
+    ));
+}
+
+#[test]
+fn test_crlf() {
+    use nom::character::complete::line_ending;
+
+    assert_eq!(lines("1\r\n2\r\n", unsigned::<u32>).unwrap(), [1, 2]);
+    assert_eq!(parse_all("1,2\r\n", list(unsigned::<u32>)).unwrap(), [1, 2]);
+
+    assert_eq!(lines("1\r\n2\r", unsigned::<u32>).unwrap(), [1, 2]);
+
+    let groups = groups("1\r\n2\r\n\r\n\r\n3\r", |group| {
+        separated_list1(line_ending, unsigned::<u32>)(group)
+    });
+    assert_eq!(groups.unwrap(), [vec![1, 2], vec![3]]);
+}
@@ -0,0 +1,81 @@
+// Random numbers for generating puzzle inputs. Generated inputs have to be reproducible from
+// their seed across versions and platforms, so this is a fixed algorithm (SplitMix64) rather
+// than whatever a random number crate currently considers best.
+
+use std::ops::RangeInclusive;
+
+/// A seeded pseudo random number generator, not suitable for anything security related.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range");
+
+        match (end - start).checked_add(1) {
+            // Multiplying instead of taking the remainder keeps the bias negligible.
+            Some(len) => start + ((u128::from(self.next_u64()) * u128::from(len)) >> 64) as u64,
+            None => self.next_u64(),
+        }
+    }
+
+    /// An index into something `len` long, which must not be empty.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "nothing to pick from");
+        self.range(0..=len as u64 - 1) as usize
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        // The top 53 bits fill the mantissa of an f64 in [0, 1).
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[test]
+fn test_rng() {
+    // The first outputs of SplitMix64 seeded with 0, they must never change.
+    let mut rng = Rng::new(0);
+    assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+    assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+
+    let mut rng = Rng::new(7);
+    for _ in 0..1000 {
+        assert!((3..=5).contains(&rng.range(3..=5)));
+        assert!(rng.index(4) < 4);
+    }
+    assert_eq!(rng.range(9..=9), 9);
+    rng.range(0..=u64::MAX);
+    assert!(!rng.chance(0.0));
+    assert!(rng.chance(1.0));
+
+    let mut items = [1, 2, 3, 4, 5];
+    rng.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, [1, 2, 3, 4, 5]);
+}
@@ -0,0 +1,257 @@
+// Graph searches over implicit graphs: a node type plus a function listing a node's neighbours,
+// so that grids, caves and puzzle states can all be searched without building a graph first.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// Walks the parent links back from `end`, the result starts at the node without parent.
+fn reconstruct<N>(parents: &HashMap<N, Option<N>>, end: N) -> Vec<N>
+where
+    N: Eq + Hash + Clone,
+{
+    let mut path = vec![end];
+    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Shortest path in number of steps from `start` to the first node satisfying `success`, both
+/// ends included.
+pub fn bfs<N, FN, IN, FS>(start: N, mut neighbors: FN, mut success: FS) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if success(&node) {
+            return Some(reconstruct(&parents, node));
+        }
+
+        for next in neighbors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// All nodes reachable from `start`, itself included, in the order a BFS visits them.
+pub fn reachable<N, FN, IN>(start: N, mut neighbors: FN) -> Vec<N>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut order = vec![start];
+    let mut next_idx = 0;
+
+    while let Some(node) = order.get(next_idx).cloned() {
+        next_idx += 1;
+
+        for next in neighbors(&node) {
+            if seen.insert(next.clone()) {
+                order.push(next);
+            }
+        }
+    }
+
+    order
+}
+
+/// Some path from `start` to a node satisfying `success`, found depth first. Not necessarily the
+/// shortest one.
+pub fn dfs<N, FN, IN, FS>(start: N, mut neighbors: FN, mut success: FS) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if success(&node) {
+            return Some(reconstruct(&parents, node));
+        }
+
+        for next in neighbors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                stack.push(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Counts the distinct paths from `start` to nodes satisfying `success`. Nodes are not marked as
+/// visited, so the graph must not have cycles: put whatever rules out walking in circles (e.g. the
+/// caves visited so far) into the node itself. A path ends at the first successful node.
+pub fn count_paths<N, FN, IN, FS>(start: N, mut neighbors: FN, mut success: FS) -> usize
+where
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut count = 0;
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if success(&node) {
+            count += 1;
+        } else {
+            stack.extend(neighbors(&node));
+        }
+    }
+
+    count
+}
+
+/// Cheapest path from `start` to the first node satisfying `success` and its cost. `neighbors`
+/// lists the neighbours of a node together with the cost of stepping onto them.
+pub fn dijkstra<N, C, FN, IN, FS>(start: N, neighbors: FN, success: FS) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar(start, neighbors, |_| C::default(), success)
+}
+
+/// Like [`dijkstra`], but explores nodes in the order of their cost so far plus `heuristic`. The
+/// heuristic must never overestimate the remaining cost, or the path found may not be the
+/// cheapest.
+pub fn astar<N, C, FN, IN, FH, FS>(
+    start: N,
+    mut neighbors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    // Nodes are referred to by their index in `nodes` so that the heap doesn't need N: Ord.
+    let mut nodes = vec![start.clone()];
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    let mut best = vec![C::default()];
+    let mut parents: Vec<Option<usize>> = vec![None];
+
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((heuristic(&start), C::default(), 0)));
+
+    while let Some(Reverse((_, cost, idx))) = queue.pop() {
+        // A cheaper way to this node was found after this entry was queued.
+        if cost > best[idx] {
+            continue;
+        }
+
+        if success(&nodes[idx]) {
+            let mut path = vec![nodes[idx].clone()];
+            let mut current = idx;
+            while let Some(parent) = parents[current] {
+                path.push(nodes[parent].clone());
+                current = parent;
+            }
+            path.reverse();
+
+            return Some((path, cost));
+        }
+
+        for (next, step) in neighbors(&nodes[idx]) {
+            let next_cost = cost + step;
+
+            let next_idx = match indices.get(&next) {
+                Some(&next_idx) if next_cost >= best[next_idx] => continue,
+                Some(&next_idx) => {
+                    best[next_idx] = next_cost;
+                    parents[next_idx] = Some(idx);
+                    next_idx
+                }
+                None => {
+                    nodes.push(next.clone());
+                    indices.insert(next.clone(), nodes.len() - 1);
+                    best.push(next_cost);
+                    parents.push(Some(idx));
+                    nodes.len() - 1
+                }
+            };
+
+            queue.push(Reverse((next_cost + heuristic(&next), next_cost, next_idx)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+fn line_neighbors(&n: &i32) -> Vec<i32> {
+    [n - 1, n + 1]
+        .into_iter()
+        .filter(|n| (0..10).contains(n))
+        .collect()
+}
+
+#[test]
+fn test_bfs() {
+    assert_eq!(bfs(2, line_neighbors, |&n| n == 5), Some(vec![2, 3, 4, 5]));
+    assert_eq!(bfs(2, line_neighbors, |&n| n == 20), None);
+    assert_eq!(reachable(0, line_neighbors).len(), 10);
+
+    let path = dfs(2, line_neighbors, |&n| n == 0).unwrap();
+    assert_eq!((path[0], path[path.len() - 1]), (2, 0));
+}
+
+#[test]
+fn test_count_paths() {
+    // Steps of one or two stairs up a flight of 5.
+    let paths = count_paths(
+        0,
+        |&n| [n + 1, n + 2].into_iter().filter(|&n| n <= 5),
+        |&n| n == 5,
+    );
+    assert_eq!(paths, 8);
+}
+
+#[test]
+fn test_dijkstra() {
+    // Going straight from 0 to 3 is more expensive than the detour over 1 and 2.
+    let edges = HashMap::from([
+        (0, vec![(1, 1), (3, 10)]),
+        (1, vec![(2, 1)]),
+        (2, vec![(3, 1)]),
+        (3, vec![]),
+    ]);
+    let neighbors = |n: &u32| edges[n].clone();
+
+    assert_eq!(
+        dijkstra(0, neighbors, |&n| n == 3),
+        Some((vec![0, 1, 2, 3], 3))
+    );
+    assert_eq!(
+        astar(0, neighbors, |&n| 3 - n, |&n| n == 3),
+        Some((vec![0, 1, 2, 3], 3))
+    );
+    assert_eq!(dijkstra(1, neighbors, |&n| n == 0), None);
+}
@@ -0,0 +1,135 @@
+// Puzzles that simulate something step by step, so that the runner can show them one step at a
+// time.
+
+use crate::error::AocError;
+
+/// A simulation the runner can step through, see [`crate::solution::Solution::simulation`].
+pub trait Simulation {
+    /// Advances by one step. Returns false, without changing anything, once the simulation is
+    /// over.
+    fn step(&mut self) -> bool;
+
+    /// Draws the current state.
+    fn render(&self) -> String;
+}
+
+/// Runs `simulation` until it is over.
+pub fn run_to_end(simulation: &mut dyn Simulation) {
+    while simulation.step() {}
+}
+
+/// Moves through a simulation in both directions. Simulations only go forward, going back
+/// restarts them and replays the steps up to the one asked for.
+pub struct Stepper<'a> {
+    restart: Box<dyn Fn() -> Result<Box<dyn Simulation + 'a>, AocError> + 'a>,
+    current: Box<dyn Simulation + 'a>,
+    step: usize,
+    // The number of steps, once the simulation ran out of them.
+    last: Option<usize>,
+}
+
+impl<'a> Stepper<'a> {
+    /// Starts the simulation returned by `restart`, which is called again whenever the stepper
+    /// has to go back.
+    pub fn new<F>(restart: F) -> Result<Stepper<'a>, AocError>
+    where
+        F: Fn() -> Result<Box<dyn Simulation + 'a>, AocError> + 'a,
+    {
+        Ok(Stepper {
+            current: restart()?,
+            restart: Box::new(restart),
+            step: 0,
+            last: None,
+        })
+    }
+
+    /// The number of steps done so far.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// The number of steps of the whole simulation, once it has been run to the end.
+    pub fn last(&self) -> Option<usize> {
+        self.last
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.last == Some(self.step)
+    }
+
+    /// Moves to `step`, or to the end of the simulation if it has fewer steps.
+    pub fn goto(&mut self, step: usize) -> Result<(), AocError> {
+        if step < self.step {
+            self.current = (self.restart)()?;
+            self.step = 0;
+        }
+
+        while self.step < step {
+            if !self.current.step() {
+                self.last = Some(self.step);
+                break;
+            }
+            self.step += 1;
+        }
+
+        Ok(())
+    }
+
+    pub fn forward(&mut self, steps: usize) -> Result<(), AocError> {
+        self.goto(self.step.saturating_add(steps))
+    }
+
+    pub fn back(&mut self, steps: usize) -> Result<(), AocError> {
+        self.goto(self.step.saturating_sub(steps))
+    }
+
+    pub fn render(&self) -> String {
+        self.current.render()
+    }
+}
+
+#[cfg(test)]
+struct Countdown(u32);
+
+#[cfg(test)]
+impl Simulation for Countdown {
+    fn step(&mut self) -> bool {
+        if self.0 == 0 {
+            return false;
+        }
+        self.0 -= 1;
+        true
+    }
+
+    fn render(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[test]
+fn test_stepper() {
+    let mut stepper = Stepper::new(|| Ok(Box::new(Countdown(5)))).unwrap();
+    assert_eq!((stepper.step(), stepper.render().as_str()), (0, "5"));
+
+    stepper.forward(2).unwrap();
+    assert_eq!((stepper.step(), stepper.render().as_str()), (2, "3"));
+    assert_eq!(stepper.last(), None);
+
+    stepper.forward(10).unwrap();
+    assert_eq!((stepper.step(), stepper.render().as_str()), (5, "0"));
+    assert_eq!(stepper.last(), Some(5));
+    assert!(stepper.is_over());
+
+    stepper.back(4).unwrap();
+    assert_eq!((stepper.step(), stepper.render().as_str()), (1, "4"));
+    assert!(!stepper.is_over());
+
+    stepper.back(4).unwrap();
+    assert_eq!(stepper.render(), "5");
+    stepper.goto(usize::MAX).unwrap();
+    assert_eq!(stepper.step(), 5);
+
+    let mut countdown = Countdown(3);
+    run_to_end(&mut countdown);
+    assert_eq!(countdown.0, 0);
+}
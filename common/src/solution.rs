@@ -0,0 +1,333 @@
+use crate::dot::Dot;
+use crate::error::AocError;
+use crate::input::read_from;
+use crate::random::Rng;
+use crate::simulation::Simulation;
+use std::any::Any;
+use std::fmt;
+use std::io::Read;
+use std::path::Path;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident as $target:ty: $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $target)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Unsigned as u64: u8, u16, u32, u64, usize);
+answer_from!(Signed as i64: i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// One day's puzzle. The input is parsed once and both parts are solved from the parsed form.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError>;
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError>;
+
+    /// Parses everything `reader` yields, for inputs coming from stdin or generated on the fly.
+    fn parse_reader<R>(reader: R) -> Result<Self::Input, AocError>
+    where
+        R: Read,
+    {
+        Self::parse(&read_from(reader, Path::new("<input>"))?)
+    }
+
+    /// Parses `input` and solves a single part of it, handy for inputs embedded in tests.
+    fn solve(input: &str, part: u32) -> Result<Answer, AocError> {
+        let input = Self::parse(input)?;
+        match part {
+            1 => Self::part1(&input),
+            2 => Self::part2(&input),
+            _ => Err(format!("there is no part {}", part).into()),
+        }
+    }
+
+    /// The simulation solving `part`, for puzzles that can be watched step by step.
+    fn simulation(_input: &Self::Input, _part: u32) -> Result<Box<dyn Simulation + '_>, AocError> {
+        Err("nothing to step through".into())
+    }
+
+    /// The structure built from the input as a Graphviz graph, for puzzles about graphs or trees.
+    fn dot(_input: &Self::Input) -> Result<Dot, AocError> {
+        Err("nothing to draw as a graph".into())
+    }
+
+    /// A random, well-formed input for stress tests. What `size` means is up to the puzzle, bigger
+    /// is harder.
+    fn generate(_rng: &mut Rng, _size: usize) -> Result<String, AocError> {
+        Err("no input generator".into())
+    }
+
+    /// A straightforward but slow way to solve `part`, to check the real solution against on
+    /// generated inputs. `None` if there is nothing to compare with.
+    fn reference(_input: &Self::Input, _part: u32) -> Option<Result<Answer, AocError>> {
+        None
+    }
+}
+
+/// Parsed input of a [`Puzzle`], only usable with the puzzle that produced it.
+pub struct Parsed(Box<dyn Any>);
+
+type ParseFn = fn(&str) -> Result<Parsed, AocError>;
+type PartFn = fn(&Parsed) -> Result<Answer, AocError>;
+type GenerateFn = fn(&mut Rng, usize) -> Result<String, AocError>;
+type ReferenceFn = fn(&Parsed, u32) -> Option<Result<Answer, AocError>>;
+type DotFn = fn(&Parsed) -> Result<Dot, AocError>;
+type SimulationFn = for<'a> fn(&'a Parsed, u32) -> Result<Box<dyn Simulation + 'a>, AocError>;
+
+/// A [`Solution`] with its input type erased, so that all days fit into one table.
+pub struct Puzzle {
+    pub day: u32,
+    parse: ParseFn,
+    part1: PartFn,
+    part2: PartFn,
+    simulation: SimulationFn,
+    dot: DotFn,
+    generate: GenerateFn,
+    reference: ReferenceFn,
+}
+
+impl Puzzle {
+    pub const fn new<S>(day: u32) -> Puzzle
+    where
+        S: Solution,
+        S::Input: 'static,
+    {
+        Puzzle {
+            day,
+            parse: parse::<S>,
+            part1: part1::<S>,
+            part2: part2::<S>,
+            simulation: simulation::<S>,
+            dot: dot::<S>,
+            generate: S::generate,
+            reference: reference::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed, AocError> {
+        (self.parse)(input)
+    }
+
+    pub fn part(&self, input: &Parsed, part: u32) -> Result<Answer, AocError> {
+        match part {
+            1 => (self.part1)(input),
+            2 => (self.part2)(input),
+            _ => Err(format!("day {} has no part {}", self.day, part).into()),
+        }
+    }
+
+    /// Like [`Puzzle::parse`], reading the input from `reader` first. `name` stands in for the
+    /// path in errors.
+    pub fn parse_reader<R>(&self, reader: R, name: &Path) -> Result<Parsed, AocError>
+    where
+        R: Read,
+    {
+        self.parse(&read_from(reader, name)?)
+            .map_err(|e| e.in_file(name))
+    }
+
+    pub fn simulation<'a>(
+        &self,
+        input: &'a Parsed,
+        part: u32,
+    ) -> Result<Box<dyn Simulation + 'a>, AocError> {
+        (self.simulation)(input, part)
+    }
+
+    pub fn dot(&self, input: &Parsed) -> Result<Dot, AocError> {
+        (self.dot)(input)
+    }
+
+    pub fn generate(&self, rng: &mut Rng, size: usize) -> Result<String, AocError> {
+        (self.generate)(rng, size)
+    }
+
+    pub fn reference(&self, input: &Parsed, part: u32) -> Option<Result<Answer, AocError>> {
+        (self.reference)(input, part)
+    }
+
+    /// Parses `input` and solves a single part of it.
+    pub fn solve(&self, input: &str, part: u32) -> Result<Answer, AocError> {
+        self.part(&self.parse(input)?, part)
+    }
+}
+
+fn parse<S>(input: &str) -> Result<Parsed, AocError>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    Ok(Parsed(Box::new(S::parse(input)?)))
+}
+
+fn downcast<S>(input: &Parsed) -> &S::Input
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    input
+        .0
+        .downcast_ref()
+        .expect("input was parsed by a different puzzle")
+}
+
+fn part1<S>(input: &Parsed) -> Result<Answer, AocError>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    S::part1(downcast::<S>(input))
+}
+
+fn part2<S>(input: &Parsed) -> Result<Answer, AocError>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    S::part2(downcast::<S>(input))
+}
+
+fn simulation<S>(input: &Parsed, part: u32) -> Result<Box<dyn Simulation + '_>, AocError>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    S::simulation(downcast::<S>(input), part)
+}
+
+fn dot<S>(input: &Parsed) -> Result<Dot, AocError>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    S::dot(downcast::<S>(input))
+}
+
+fn reference<S>(input: &Parsed, part: u32) -> Option<Result<Answer, AocError>>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    S::reference(downcast::<S>(input), part)
+}
+
+/// Stack size for threads solving puzzles. Some solutions keep large grids on the stack, more
+/// than the 2 MiB spawned threads get by default.
+pub const STACK_SIZE: usize = 16 * 1024 * 1024;
+
+/// All puzzles solved for one year.
+pub struct Year {
+    pub year: u32,
+    pub input_dir: &'static str,
+    pub puzzles: &'static [Puzzle],
+}
+
+impl Year {
+    pub fn puzzle(&self, day: u32) -> Option<&Puzzle> {
+        self.puzzles.iter().find(|p| p.day == day)
+    }
+}
+
+/// Looks up the puzzle for `day` of `year` in `years`.
+pub fn find<'a>(years: &[&'a Year], year: u32, day: u32) -> Option<(&'a Year, &'a Puzzle)> {
+    let year = years.iter().find(|y| y.year == year)?;
+
+    Some((year, year.puzzle(day)?))
+}
+
+#[cfg(test)]
+struct Sum;
+
+#[cfg(test)]
+impl Solution for Sum {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input
+            .split(',')
+            .map(|s| crate::error::parse_at(input, s))
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(input.iter().sum::<i32>().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(format!("{} numbers", input.len()).into())
+    }
+}
+
+#[test]
+fn test_puzzle() {
+    const YEAR: Year = Year {
+        year: 2000,
+        input_dir: "",
+        puzzles: &[Puzzle::new::<Sum>(3)],
+    };
+
+    let (_, puzzle) = find(&[&YEAR], 2000, 3).unwrap();
+    let parsed = puzzle.parse("1,2,-4").unwrap();
+
+    assert_eq!(puzzle.part(&parsed, 1).unwrap(), Answer::Signed(-1));
+    assert_eq!(puzzle.part(&parsed, 2).unwrap().to_string(), "3 numbers");
+    assert!(puzzle.part(&parsed, 3).is_err());
+    assert!(puzzle.solve("1,x", 1).is_err());
+    assert!(puzzle.simulation(&parsed, 1).is_err());
+    assert!(puzzle.dot(&parsed).is_err());
+    assert!(puzzle.generate(&mut Rng::new(1), 10).is_err());
+    assert!(puzzle.reference(&parsed, 1).is_none());
+    assert!(find(&[&YEAR], 2000, 4).is_none());
+
+    let parsed = puzzle
+        .parse_reader("4,5".as_bytes(), Path::new("-"))
+        .unwrap();
+    assert_eq!(puzzle.part(&parsed, 1).unwrap(), Answer::Signed(9));
+}
+
+#[test]
+fn test_solution() {
+    assert_eq!(Sum::solve("1,2,-4", 1).unwrap(), Answer::Signed(-1));
+    assert!(Sum::solve("1,2,-4", 3).is_err());
+    assert_eq!(Sum::parse_reader(&b"7,8"[..]).unwrap(), vec![7, 8]);
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// A point on an unbounded plane. x grows to the right and y grows downwards, like the puzzle
+/// descriptions draw them.
+pub type Point = (i64, i64);
+
+/// Cells scattered over an unbounded plane. Only occupied points are stored, and the bounding
+/// box of all of them is kept up to date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    // Top left and bottom right corner, both inclusive.
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    /// Stores `value` at `point` and returns what was there before.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        let (x, y) = point;
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some(((x0, y0), (x1, y1))) => ((x0.min(x), y0.min(y)), (x1.max(x), y1.max(y))),
+        });
+
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let removed = self.cells.remove(&point)?;
+
+        // Only a point on the edge can make the bounding box shrink.
+        if let Some(((x0, y0), (x1, y1))) = self.bounds {
+            if point.0 == x0 || point.0 == x1 || point.1 == y0 || point.1 == y1 {
+                self.bounds = bounding_box(self.cells.keys().copied());
+            }
+        }
+
+        Some(removed)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Occupied points with their values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The top left and bottom right corner (inclusive) of all occupied points.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    /// Draws the rectangle between `top_left` and `bottom_right` (inclusive), one line per row
+    /// with a newline after every row. Empty points are drawn as `.`.
+    pub fn render_area(&self, top_left: Point, bottom_right: Point) -> String
+    where
+        T: fmt::Display,
+    {
+        let mut out = String::new();
+
+        for y in top_left.1..=bottom_right.1 {
+            for x in top_left.0..=bottom_right.0 {
+                match self.get((x, y)) {
+                    Some(value) => out.push_str(&value.to_string()),
+                    None => out.push('.'),
+                }
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+fn bounding_box(points: impl Iterator<Item = Point>) -> Option<(Point, Point)> {
+    points.fold(None, |bounds, (x, y)| match bounds {
+        None => Some(((x, y), (x, y))),
+        Some(((x0, y0), (x1, y1))) => Some(((x0.min(x), y0.min(y)), (x1.max(x), y1.max(y)))),
+    })
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (Point, T)>,
+    {
+        let mut grid = SparseGrid::new();
+        for (point, value) in iter {
+            grid.insert(point, value);
+        }
+        grid
+    }
+}
+
+/// Draws the bounding box of all occupied points, see [`SparseGrid::render_area`].
+impl<T> fmt::Display for SparseGrid<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.bounds {
+            Some((top_left, bottom_right)) => {
+                write!(f, "{}", self.render_area(top_left, bottom_right))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn test_bounds() {
+    let mut grid = SparseGrid::new();
+    assert_eq!(grid.bounds(), None);
+
+    grid.insert((0, 0), '#');
+    grid.insert((-2, 1), '#');
+    grid.insert((3, -1), '#');
+    assert_eq!(grid.bounds(), Some(((-2, -1), (3, 1))));
+
+    assert_eq!(grid.remove((3, -1)), Some('#'));
+    assert_eq!(grid.remove((3, -1)), None);
+    assert_eq!(grid.bounds(), Some(((-2, 0), (0, 1))));
+    assert_eq!(grid.len(), 2);
+}
+
+#[test]
+fn test_render() {
+    let grid: SparseGrid<char> = [((-1, 0), '#'), ((1, 1), 'H')].into_iter().collect();
+
+    assert_eq!(grid.to_string(), "#..\n..H\n");
+    assert_eq!(grid.render_area((0, 0), (2, 0)), "...\n");
+    assert_eq!(SparseGrid::<char>::new().to_string(), "");
+}
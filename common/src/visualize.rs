@@ -0,0 +1,280 @@
+// Frames drawn by simulations, written out as an animated GIF or a numbered PPM sequence.
+//
+// Solutions call `frame` whenever there is something worth showing. Nothing is drawn unless the
+// runner started recording, so the hooks can stay in the solutions for good, like log calls.
+
+use crate::grid::Grid;
+use crate::sparse::{Point, SparseGrid};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const GREY: Rgb = [96, 96, 96];
+pub const RED: Rgb = [220, 50, 47];
+pub const YELLOW: Rgb = [250, 200, 40];
+pub const BLUE: Rgb = [38, 139, 210];
+pub const NAVY: Rgb = [0, 20, 60];
+
+// More frames are thinned out, see `frame`. A frame of a few hundred cells squared is already
+// hundreds of kilobytes.
+const MAX_FRAMES: usize = 256;
+
+// The longer side of a GIF or PPM frame is scaled up to about this many pixels.
+const TARGET_SIZE: usize = 512;
+const MAX_SCALE: usize = 16;
+
+// Time between GIF frames, in hundredths of a second.
+const DELAY: u16 = 5;
+
+struct Recorder {
+    frames: Vec<Grid<Rgb>>,
+    // Only every `stride`th frame is kept.
+    stride: usize,
+    seen: usize,
+}
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+/// Starts recording frames, dropping any recorded before.
+pub fn start() {
+    *RECORDER.lock().unwrap() = Some(Recorder {
+        frames: Vec::new(),
+        stride: 1,
+        seen: 0,
+    });
+}
+
+/// Whether frames are being recorded, for solutions that need some preparation before drawing.
+pub fn is_recording() -> bool {
+    RECORDER.lock().unwrap().is_some()
+}
+
+/// Records the frame drawn by `draw`, which is only called while recording. Long simulations
+/// keep an evenly spaced selection: once the limit is reached every other frame is dropped and
+/// only every other frame from then on is drawn.
+pub fn frame<F>(draw: F)
+where
+    F: FnOnce() -> Grid<Rgb>,
+{
+    let mut recorder = RECORDER.lock().unwrap();
+    let Some(recorder) = recorder.as_mut() else {
+        return;
+    };
+
+    recorder.seen += 1;
+    if (recorder.seen - 1) % recorder.stride != 0 {
+        return;
+    }
+
+    recorder.frames.push(draw());
+    if recorder.frames.len() == MAX_FRAMES {
+        recorder.frames = recorder.frames.drain(..).step_by(2).collect();
+        recorder.stride *= 2;
+    }
+}
+
+/// Stops recording and returns the frames.
+pub fn finish() -> Vec<Grid<Rgb>> {
+    RECORDER
+        .lock()
+        .unwrap()
+        .take()
+        .map_or_else(Vec::new, |recorder| recorder.frames)
+}
+
+/// Mixes `from` and `to`, `t` is 0 for `from` and 1 for `to`.
+pub fn blend(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    [0, 1, 2].map(|i| (f64::from(from[i]) + (f64::from(to[i]) - f64::from(from[i])) * t) as u8)
+}
+
+/// Draws the rectangle between `top_left` and `bottom_right` (inclusive) of `grid`, `color`
+/// gets `None` for empty points.
+pub fn draw_sparse<T, F>(
+    grid: &SparseGrid<T>,
+    top_left: Point,
+    bottom_right: Point,
+    mut color: F,
+) -> Grid<Rgb>
+where
+    F: FnMut(Point, Option<&T>) -> Rgb,
+{
+    let width = usize::try_from(bottom_right.0 - top_left.0 + 1).unwrap_or(0);
+    let height = usize::try_from(bottom_right.1 - top_left.1 + 1).unwrap_or(0);
+    let mut frame = Grid::new(width, height, BLACK);
+
+    for (row, col) in frame.positions().collect::<Vec<_>>() {
+        let point = (top_left.0 + col as i64, top_left.1 + row as i64);
+        frame[(row, col)] = color(point, grid.get(point));
+    }
+
+    frame
+}
+
+// All frames are drawn on a canvas as large as the largest of them, in their top left corner.
+struct Canvas {
+    width: usize,
+    height: usize,
+    scale: usize,
+}
+
+impl Canvas {
+    fn new(frames: &[Grid<Rgb>]) -> io::Result<Canvas> {
+        if frames.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no frames recorded",
+            ));
+        }
+
+        let width = frames.iter().map(Grid::width).max().unwrap_or(0).max(1);
+        let height = frames.iter().map(Grid::height).max().unwrap_or(0).max(1);
+        let scale = (TARGET_SIZE / width.max(height)).clamp(1, MAX_SCALE);
+
+        Ok(Canvas {
+            width: width * scale,
+            height: height * scale,
+            scale,
+        })
+    }
+
+    // The frame as RGB bytes, row by row.
+    fn pixels(&self, frame: &Grid<Rgb>) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.width * self.height * 3);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let color = frame
+                    .get((y / self.scale, x / self.scale))
+                    .unwrap_or(&BLACK);
+                pixels.extend_from_slice(color);
+            }
+        }
+
+        pixels
+    }
+}
+
+/// Writes `frames` as an animated GIF that loops forever.
+pub fn write_gif(path: &Path, frames: &[Grid<Rgb>]) -> io::Result<()> {
+    let canvas = Canvas::new(frames)?;
+    let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "frames too large for a GIF");
+    let width = u16::try_from(canvas.width).map_err(|_| too_large())?;
+    let height = u16::try_from(canvas.height).map_err(|_| too_large())?;
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = gif::Encoder::new(file, width, height, &[]).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    for frame in frames {
+        let mut frame = gif::Frame::from_rgb_speed(width, height, &canvas.pixels(frame), 30);
+        frame.delay = DELAY;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
+
+// `out.ppm` becomes `out_0000.ppm`, `out_0001.ppm`, ...
+fn numbered(path: &Path, n: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}_{:04}.ppm", stem, n))
+}
+
+/// Writes every frame to a PPM file of its own, numbered after `path`.
+pub fn write_ppm(path: &Path, frames: &[Grid<Rgb>]) -> io::Result<()> {
+    let canvas = Canvas::new(frames)?;
+
+    for (n, frame) in frames.iter().enumerate() {
+        let mut file = BufWriter::new(File::create(numbered(path, n))?);
+        write!(file, "P6\n{} {}\n255\n", canvas.width, canvas.height)?;
+        file.write_all(&canvas.pixels(frame))?;
+        file.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Writes `frames` in the format matching the extension of `path`, `.gif` or `.ppm`.
+pub fn write(path: &Path, frames: &[Grid<Rgb>]) -> io::Result<()> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("gif") => write_gif(path, frames),
+        Some("ppm") => write_ppm(path, frames),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{}: expected a .gif or .ppm file", path.display()),
+        )),
+    }
+}
+
+#[test]
+fn test_recording() {
+    frame(|| unreachable!("not recording"));
+
+    start();
+    assert!(is_recording());
+    for n in 0..MAX_FRAMES * 2 + 1 {
+        frame(|| Grid::new(1, 1, [(n >> 8) as u8, n as u8, 0]));
+    }
+    let frames = finish();
+    assert!(!is_recording());
+
+    // Every fourth frame is left after thinning out twice.
+    assert_eq!(frames.len(), MAX_FRAMES / 2 + 1);
+    assert_eq!(frames[1][(0, 0)], [0, 4, 0]);
+    assert_eq!(frames.last().unwrap()[(0, 0)], [2, 0, 0]);
+}
+
+#[test]
+fn test_draw() {
+    assert_eq!(blend(BLACK, WHITE, 0.5), [127, 127, 127]);
+    assert_eq!(blend(BLACK, WHITE, 2.0), WHITE);
+
+    let grid: SparseGrid<char> = [((-1, 0), '#'), ((1, 1), '#')].into_iter().collect();
+    let frame = draw_sparse(&grid, (-1, 0), (1, 1), |_, c| match c {
+        Some(_) => WHITE,
+        None => BLACK,
+    });
+    assert_eq!((frame.width(), frame.height()), (3, 2));
+    assert_eq!(frame[(0, 0)], WHITE);
+    assert_eq!(frame[(1, 2)], WHITE);
+    assert_eq!(frame[(1, 1)], BLACK);
+}
+
+#[test]
+fn test_write() {
+    use std::fs;
+
+    let dir = std::env::temp_dir().join(format!("aoc-visualize-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let frames = [Grid::new(2, 1, RED), Grid::new(1, 2, BLUE)];
+
+    write(&dir.join("out.gif"), &frames).unwrap();
+    assert!(fs::read(dir.join("out.gif"))
+        .unwrap()
+        .starts_with(b"GIF89a"));
+
+    write(&dir.join("out.ppm"), &frames).unwrap();
+    let ppm = fs::read(dir.join("out_0001.ppm")).unwrap();
+    // Both frames are padded to 2x2 cells, scaled up to 32x32 pixels.
+    assert!(ppm.starts_with(b"P6\n32 32\n255\n"));
+    assert_eq!(ppm.len(), 13 + 32 * 32 * 3);
+    let pixel = |x: usize, y: usize| &ppm[13 + (y * 32 + x) * 3..][..3];
+    assert_eq!(pixel(0, 0), BLUE);
+    assert_eq!(pixel(0, 31), BLUE);
+    assert_eq!(pixel(31, 0), BLACK);
+    assert!(!dir.join("out_0002.ppm").exists());
+
+    assert!(write(&dir.join("out.png"), &frames).is_err());
+    assert!(write(&dir.join("empty.gif"), &[]).is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+// 2021 day 16, the BITS transmission is decoded from hex before `packet` gets to it.
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = aoc2021::YEAR.puzzle(16).unwrap().parse(input);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+// 2022 day 9, every line is a rope motion for `parse_action`.
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = aoc2022::YEAR.puzzle(9).unwrap().parse(input);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+// 2022 day 10, every line is an instruction for `parse_instruction`.
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = aoc2022::YEAR.puzzle(10).unwrap().parse(input);
+});
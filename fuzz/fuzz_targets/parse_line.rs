@@ -0,0 +1,8 @@
+#![no_main]
+
+// 2022 day 7, the terminal output goes through `parse_line` line by line.
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = aoc2022::YEAR.puzzle(7).unwrap().parse(input);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+// 2022 day 11, `parsers::groups` hands each blank line separated group to `parse_monkey`.
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = aoc2022::YEAR.puzzle(11).unwrap().parse(input);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+// 2021 day 18, every line is a `snailfish_number`.
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = aoc2021::YEAR.puzzle(18).unwrap().parse(input);
+});
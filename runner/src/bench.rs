@@ -0,0 +1,171 @@
+use aoc_common::input::{input_path, read_string};
+use aoc_common::solution::{Puzzle, Year};
+use serde::Serialize;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Serialize)]
+struct Stats {
+    min_ns: u128,
+    median_ns: u128,
+    max_ns: u128,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+
+        Stats {
+            min_ns: samples[0].as_nanos(),
+            median_ns: samples[samples.len() / 2].as_nanos(),
+            max_ns: samples[samples.len() - 1].as_nanos(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DayReport {
+    year: u32,
+    day: u32,
+    input: String,
+    iterations: u32,
+    parse: Stats,
+    part1: Stats,
+    part2: Stats,
+}
+
+#[derive(Serialize)]
+struct Report {
+    days: Vec<DayReport>,
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn bench_day(
+    year: &Year,
+    puzzle: &Puzzle,
+    input: &Path,
+    iterations: u32,
+) -> Result<DayReport, Box<dyn Error>> {
+    let text = read_string(input)?;
+
+    let mut parse = Vec::new();
+    let mut part1 = Vec::new();
+    let mut part2 = Vec::new();
+
+    for _ in 0..iterations {
+        let (parsed, elapsed) = time(|| puzzle.parse(&text));
+        let parsed = parsed.map_err(|e| e.in_file(input))?;
+        parse.push(elapsed);
+
+        let (answer, elapsed) = time(|| puzzle.part(&parsed, 1));
+        answer?;
+        part1.push(elapsed);
+
+        let (answer, elapsed) = time(|| puzzle.part(&parsed, 2));
+        answer?;
+        part2.push(elapsed);
+    }
+
+    Ok(DayReport {
+        year: year.year,
+        day: puzzle.day,
+        input: input.file_name().unwrap().to_string_lossy().into_owned(),
+        iterations,
+        parse: Stats::new(parse),
+        part1: Stats::new(part1),
+        part2: Stats::new(part2),
+    })
+}
+
+fn print_table(reports: &[DayReport]) {
+    println!(
+        "{:<4}  {:>3}  {:<5}  {:>12}  {:>12}  {:>12}",
+        "year", "day", "phase", "min", "median", "max"
+    );
+
+    for report in reports {
+        for (phase, stats) in [
+            ("parse", &report.parse),
+            ("part1", &report.part1),
+            ("part2", &report.part2),
+        ] {
+            println!(
+                "{:<4}  {:>3}  {:<5}  {:>12.2?}  {:>12.2?}  {:>12.2?}",
+                report.year,
+                report.day,
+                phase,
+                Duration::from_nanos(stats.min_ns as u64),
+                Duration::from_nanos(stats.median_ns as u64),
+                Duration::from_nanos(stats.max_ns as u64),
+            );
+        }
+    }
+}
+
+/// Times parse, part 1 and part 2 of the selected days separately, `iterations` times each.
+/// Days without an input file are skipped.
+pub fn bench(
+    years: &[&Year],
+    year: Option<u32>,
+    day: Option<u32>,
+    example: Option<u32>,
+    iterations: u32,
+    json: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    if iterations == 0 {
+        return Err("need at least one iteration".into());
+    }
+
+    let mut reports = Vec::new();
+
+    for y in years
+        .iter()
+        .filter(|y| year.is_none_or(|year| y.year == year))
+    {
+        for puzzle in y
+            .puzzles
+            .iter()
+            .filter(|p| day.is_none_or(|day| p.day == day))
+        {
+            let input = input_path(Path::new(y.input_dir), puzzle.day, example);
+            if !input.exists() {
+                continue;
+            }
+
+            reports.push(bench_day(y, puzzle, &input, iterations)?);
+        }
+    }
+
+    if reports.is_empty() {
+        return Err("nothing to benchmark".into());
+    }
+
+    print_table(&reports);
+
+    if let Some(json) = json {
+        fs::write(
+            json,
+            serde_json::to_string_pretty(&Report { days: reports })?,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_stats() {
+    let stats = Stats::new(vec![
+        Duration::from_nanos(30),
+        Duration::from_nanos(10),
+        Duration::from_nanos(20),
+    ]);
+
+    assert_eq!((stats.min_ns, stats.median_ns, stats.max_ns), (10, 20, 30));
+}
@@ -0,0 +1,190 @@
+use aoc_common::input::read_string;
+use serde::Deserialize;
+use std::env;
+use std::error::Error;
+use std::path::PathBuf;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings for talking to the Advent of Code website, read from `~/.config/aoc/config.toml`
+/// (or the file in `AOC_CONFIG`). `AOC_SESSION` and `AOC_BASE_URL` take precedence over it.
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "https://adventofcode.com"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    session: Option<String>,
+    base_url: Option<String>,
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("AOC_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("aoc").join("config.toml"))
+}
+
+impl Config {
+    pub fn load() -> Result<Config, Box<dyn Error>> {
+        let mut config = match config_path() {
+            Some(path) if path.exists() => toml::from_str(&read_string(&path)?)
+                .map_err(|e| format!("{}: {}", path.display(), e))?,
+            _ => Config::default(),
+        };
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = Some(base_url);
+        }
+
+        Ok(config)
+    }
+}
+
+/// A logged in session on the website.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            agent: ureq::AgentBuilder::new()
+                .user_agent("github.com/keytoaster/advent_of_code")
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// A client for the configured session, talking to `base_url` if given, else to the
+    /// configured one.
+    pub fn from_config(config: &Config, base_url: Option<&str>) -> Result<Client, Box<dyn Error>> {
+        let session = config
+            .session
+            .as_deref()
+            .ok_or("no session token, set AOC_SESSION or `session` in ~/.config/aoc/config.toml")?;
+        let base_url = base_url
+            .or(config.base_url.as_deref())
+            .unwrap_or(DEFAULT_BASE_URL);
+
+        Ok(Client::new(base_url, session))
+    }
+
+    fn url(&self, year: u32, day: u32, page: &str) -> String {
+        format!("{}/{}/day/{}{}", self.base_url, year, day, page)
+    }
+
+    /// Downloads the real input of `day`.
+    pub fn input(&self, year: u32, day: u32) -> Result<String, Box<dyn Error>> {
+        let url = self.url(year, day, "/input");
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| request_error(&url, e))?;
+
+        Ok(response.into_string()?)
+    }
+
+    /// Posts `answer` for `part` of `day` and returns the page the website answers with.
+    pub fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let url = self.url(year, day, "/answer");
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| request_error(&url, e))?;
+
+        Ok(response.into_string()?)
+    }
+}
+
+fn request_error(url: &str, e: ureq::Error) -> Box<dyn Error> {
+    match e {
+        ureq::Error::Status(status, response) => {
+            let body = response.into_string().unwrap_or_default();
+            format!(
+                "{} answered {}: {}",
+                url,
+                status,
+                body.lines().next().unwrap_or_default()
+            )
+            .into()
+        }
+        ureq::Error::Transport(e) => format!("could not reach {}: {}", url, e).into(),
+    }
+}
+
+/// Serves one canned `(status, body)` response per connection on a local port. Returns the base
+/// URL to use and a handle that yields the requests received, once all responses are sent.
+#[cfg(test)]
+pub fn stub_server(
+    responses: Vec<(u16, &'static str)>,
+) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                if line.trim_end().is_empty() {
+                    break;
+                }
+                request.push_str(&line);
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+            requests.push(request);
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+
+        requests
+    });
+
+    (base_url, handle)
+}
@@ -0,0 +1,70 @@
+use crate::client::Client;
+use aoc_common::input::input_path;
+use log::debug;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The real input of `day` if it is already in `input_dir`. Empty files, as left by `aoc new`,
+/// don't count.
+pub fn cached(input_dir: &Path, day: u32) -> Option<PathBuf> {
+    let path = input_path(input_dir, day, None);
+    fs::metadata(&path)
+        .is_ok_and(|meta| meta.len() > 0)
+        .then_some(path)
+}
+
+/// Makes sure the real input of `day` is in `input_dir` and returns its path. Inputs already
+/// [`cached`] are never downloaded again.
+pub fn fetch(
+    client: &Client,
+    input_dir: &Path,
+    year: u32,
+    day: u32,
+) -> Result<PathBuf, Box<dyn Error>> {
+    if let Some(path) = cached(input_dir, day) {
+        debug!("using cached {}", path.display());
+        return Ok(path);
+    }
+
+    let path = input_path(input_dir, day, None);
+    let input = client.input(year, day)?;
+
+    // Write next to the destination first, so that an interrupted download never looks cached.
+    let partial = path.with_extension("part");
+    fs::write(&partial, input)?;
+    fs::rename(&partial, &path)?;
+
+    Ok(path)
+}
+
+#[test]
+fn test_fetch() {
+    let (base_url, server) = crate::client::stub_server(vec![(200, "1\n2\n3\n")]);
+    let client = Client::new(&base_url, "abc");
+
+    let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("day01"), "").unwrap();
+
+    // The empty file is replaced, the second call doesn't reach the server.
+    for _ in 0..2 {
+        let path = fetch(&client, &dir, 2022, 1).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "1\n2\n3\n");
+    }
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /2022/day/1/input "));
+    assert!(requests[0].contains("session=abc"));
+
+    // Errors from the server are reported instead of being cached.
+    let (base_url, server) = crate::client::stub_server(vec![(404, "Not Found")]);
+    let client = Client::new(&base_url, "abc");
+    let e = fetch(&client, &dir, 2022, 2).unwrap_err();
+    assert!(e.to_string().contains("404"), "{}", e);
+    assert!(!dir.join("day02").exists());
+    server.join().unwrap();
+
+    fs::remove_dir_all(dir).unwrap();
+}
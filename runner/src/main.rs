@@ -0,0 +1,392 @@
+use aoc_common::input::{input_path, read_string};
+use aoc_common::simulation::Stepper;
+use aoc_common::solution::{self, Answer, Parsed, Puzzle, Year};
+use aoc_common::visualize;
+use clap::{Parser, Subcommand};
+use client::{Client, Config};
+use log::LevelFilter;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod bench;
+mod client;
+mod fetch;
+mod run_all;
+mod scaffold;
+mod step;
+mod stress;
+mod submit;
+mod verify;
+
+const YEARS: &[&Year] = &[&aoc2021::YEAR, &aoc2022::YEAR];
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Show debug output of the solutions, twice to also trace every step. `RUST_LOG` takes
+    /// precedence.
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day, or all of them with --all.
+    Run {
+        #[arg(required_unless_present = "all")]
+        year: Option<u32>,
+        #[arg(required_unless_present = "all")]
+        day: Option<u32>,
+        /// Only solve this part.
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+        /// Use the example input instead of the real one. `--example 2` picks dayNN_example2.
+        #[arg(long, num_args = 0..=1, default_missing_value = "1")]
+        example: Option<u32>,
+        /// Read the input from this file instead, `-` reads stdin.
+        #[arg(long, conflicts_with = "example")]
+        input: Option<PathBuf>,
+        /// Write the frames drawn by the simulation to this file, an animated `.gif` or a `.ppm`
+        /// sequence (`out.ppm` becomes out_0000.ppm, out_0001.ppm, ...).
+        #[arg(long, value_name = "FILE")]
+        visualize: Option<PathBuf>,
+        /// Instead of solving, write the graph or tree built from the input to this file as
+        /// Graphviz DOT, `-` prints it.
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["part", "visualize"])]
+        dot: Option<PathBuf>,
+        /// Solve every registered day in parallel and print a summary table.
+        #[arg(long, conflicts_with_all = ["year", "day", "part", "input", "visualize", "dot"])]
+        all: bool,
+        /// With --all, only solve the days of this year.
+        #[arg(long = "year", id = "only_year")]
+        only_year: Option<u32>,
+        /// With --all, give up on a day after this many seconds. Defaults to a minute.
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// With --all, the number of days solved at once. Defaults to the number of cores.
+        #[arg(long)]
+        jobs: Option<usize>,
+    },
+    /// Time parsing and both parts separately.
+    Bench {
+        /// Only benchmark this year.
+        year: Option<u32>,
+        /// Only benchmark this day.
+        day: Option<u32>,
+        /// Use the example input instead of the real one.
+        #[arg(long, num_args = 0..=1, default_missing_value = "1")]
+        example: Option<u32>,
+        /// How many times to run each phase.
+        #[arg(long, short = 'n', default_value_t = 10)]
+        iterations: u32,
+        /// Also write the results to this file as JSON.
+        #[arg(long)]
+        json: Option<PathBuf>,
+    },
+    /// Step through the simulation of a day interactively.
+    Step {
+        year: u32,
+        day: u32,
+        /// Step through this part.
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+        /// Use the example input instead of the real one. `--example 2` picks dayNN_example2.
+        #[arg(long, num_args = 0..=1, default_missing_value = "1")]
+        example: Option<u32>,
+        /// Read the input from this file instead.
+        #[arg(long, conflicts_with = "example")]
+        input: Option<PathBuf>,
+    },
+    /// Print a random input for a day, or solve a batch of them with --stress.
+    Generate {
+        year: u32,
+        day: u32,
+        /// Seed of the first input, picked at random if not given.
+        #[arg(long)]
+        seed: Option<u64>,
+        /// How large the input is, what exactly that means depends on the day.
+        #[arg(long, default_value_t = 10)]
+        size: usize,
+        /// Solve this many inputs, with seeds counting up, and report those that fail or whose
+        /// answers differ from the day's slow reference solution.
+        #[arg(long, value_name = "RUNS")]
+        stress: Option<u64>,
+    },
+    /// Check all days against the accepted answers.
+    Verify {
+        /// Only check this year.
+        #[arg(long)]
+        year: Option<u32>,
+    },
+    /// Start a new day from the template, with empty input and example files.
+    New {
+        year: u32,
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    /// Download the real input of a day, unless it is already there.
+    Fetch {
+        year: u32,
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+        /// Download from this server instead of the configured one.
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    /// Submit an answer, refusing answers that earlier guesses already ruled out.
+    Submit {
+        year: u32,
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+        /// The answer to submit, by default the one computed for the real input.
+        answer: Option<String>,
+        /// Submit to this server instead of the configured one.
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+}
+
+fn print_answer(part: u32, answer: &Answer) {
+    match answer {
+        // Pictures start on their own line so that they stay aligned.
+        Answer::Text(text) if text.contains('\n') => print!("Part {}:\n{}", part, text),
+        _ => println!("Part {}: {}", part, answer),
+    }
+}
+
+// Parses the input of `puzzle`: `input` if given, `-` being stdin, otherwise the real input or
+// an example.
+fn parse_input(
+    year: &Year,
+    puzzle: &Puzzle,
+    example: Option<u32>,
+    input: Option<PathBuf>,
+) -> Result<Parsed, Box<dyn Error>> {
+    let input = input.unwrap_or_else(|| input_path(Path::new(year.input_dir), puzzle.day, example));
+
+    if input == Path::new("-") {
+        return Ok(puzzle.parse_reader(io::stdin().lock(), Path::new("<stdin>"))?);
+    }
+
+    Ok(puzzle
+        .parse(&read_string(&input)?)
+        .map_err(|e| e.in_file(&input))?)
+}
+
+fn run(
+    year: u32,
+    day: u32,
+    part: Option<u32>,
+    example: Option<u32>,
+    input: Option<PathBuf>,
+    visualize: Option<PathBuf>,
+    dot: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let (year, puzzle) = solution::find(YEARS, year, day)
+        .ok_or_else(|| format!("no solution for {} day {}", year, day))?;
+    let parsed = parse_input(year, puzzle, example, input)?;
+
+    if let Some(path) = dot {
+        let graph = puzzle
+            .dot(&parsed)
+            .map_err(|e| format!("day {}: {}", day, e))?;
+        if path == Path::new("-") {
+            print!("{}", graph);
+        } else {
+            fs::write(&path, graph.to_string())
+                .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        }
+        return Ok(());
+    }
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    if visualize.is_some() {
+        visualize::start();
+    }
+
+    for part in parts {
+        print_answer(part, &puzzle.part(&parsed, part)?);
+    }
+
+    if let Some(path) = visualize {
+        let frames = visualize::finish();
+        if frames.is_empty() {
+            return Err(format!("day {} doesn't draw anything", day).into());
+        }
+        visualize::write(&path, &frames)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        println!("Wrote {} frames to {}", frames.len(), path.display());
+    }
+
+    Ok(())
+}
+
+fn step(
+    year: u32,
+    day: u32,
+    part: u32,
+    example: Option<u32>,
+    input: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let (year, puzzle) = solution::find(YEARS, year, day)
+        .ok_or_else(|| format!("no solution for {} day {}", year, day))?;
+    let parsed = parse_input(year, puzzle, example, input)?;
+
+    let mut stepper = Stepper::new(|| puzzle.simulation(&parsed, part))
+        .map_err(|e| format!("day {}: {}", day, e))?;
+    let title = format!("{} day {} part {}", year.year, day, part);
+
+    step::interactive(&title, &mut stepper)
+}
+
+fn generate(
+    year: u32,
+    day: u32,
+    seed: Option<u64>,
+    size: usize,
+    stress: Option<u64>,
+) -> Result<(), Box<dyn Error>> {
+    let (_, puzzle) = solution::find(YEARS, year, day)
+        .ok_or_else(|| format!("no solution for {} day {}", year, day))?;
+
+    let seed = seed.unwrap_or_else(|| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let seed = now.as_secs() ^ u64::from(now.subsec_nanos());
+        // Without it a failure couldn't be reproduced.
+        eprintln!("seed {}", seed);
+        seed
+    });
+
+    match stress {
+        Some(runs) => stress::stress(puzzle, seed, size, runs),
+        None => stress::generate(puzzle, seed, size),
+    }
+}
+
+fn fetch(year: u32, day: u32, base_url: Option<String>) -> Result<(), Box<dyn Error>> {
+    let input_dir = YEARS
+        .iter()
+        .find(|y| y.year == year)
+        .ok_or_else(|| format!("no crate for {}", year))?
+        .input_dir;
+    let input_dir = Path::new(input_dir);
+
+    // Cached inputs don't need a session.
+    let path = match fetch::cached(input_dir, day) {
+        Some(path) => path,
+        None => {
+            let client = Client::from_config(&Config::load()?, base_url.as_deref())?;
+            fetch::fetch(&client, input_dir, year, day)?
+        }
+    };
+    println!("{}", path.display());
+
+    Ok(())
+}
+
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+
+    env_logger::Builder::new()
+        .filter_level(level)
+        .format_timestamp(None)
+        .parse_default_env()
+        .init();
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging(cli.verbose);
+
+    let result = match cli.command {
+        Command::Run {
+            all: true,
+            only_year,
+            example,
+            timeout,
+            jobs,
+            ..
+        } => run_all::run_all(
+            YEARS,
+            only_year,
+            example,
+            jobs,
+            Duration::from_secs(timeout.unwrap_or(60)),
+        ),
+        Command::Run {
+            year: Some(year),
+            day: Some(day),
+            part,
+            example,
+            input,
+            visualize,
+            dot,
+            only_year: None,
+            timeout: None,
+            jobs: None,
+            ..
+        } => run(year, day, part, example, input, visualize, dot),
+        // clap already requires the year and day without --all.
+        Command::Run { .. } => Err("--year, --timeout and --jobs only go with --all".into()),
+        Command::Step {
+            year,
+            day,
+            part,
+            example,
+            input,
+        } => step(year, day, part, example, input),
+        Command::Generate {
+            year,
+            day,
+            seed,
+            size,
+            stress,
+        } => generate(year, day, seed, size, stress),
+        Command::Bench {
+            year,
+            day,
+            example,
+            iterations,
+            json,
+        } => bench::bench(YEARS, year, day, example, iterations, json),
+        Command::Verify { year } => verify::verify(YEARS, year),
+        Command::New { year, day } => scaffold::new_day(YEARS, year, day),
+        Command::Fetch {
+            year,
+            day,
+            base_url,
+        } => fetch(year, day, base_url),
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            base_url,
+        } => submit::submit(YEARS, year, day, part, answer, base_url),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
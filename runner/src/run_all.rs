@@ -0,0 +1,370 @@
+use aoc_common::answers::escape;
+use aoc_common::input::{input_path, read_string};
+use aoc_common::solution::{Puzzle, Year, STACK_SIZE};
+use std::any::Any;
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+enum Status {
+    Ok,
+    // No input file.
+    Missing,
+    Error(String),
+    Panic(String),
+    Timeout,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Missing => write!(f, "missing"),
+            Status::Error(e) => write!(f, "ERROR    {}", e),
+            Status::Panic(message) => write!(f, "PANIC    {}", message),
+            Status::Timeout => write!(f, "TIMEOUT"),
+        }
+    }
+}
+
+struct DayResult {
+    year: u32,
+    day: u32,
+    answers: [String; 2],
+    status: Status,
+    elapsed: Duration,
+}
+
+// Answers of both parts, as shown in the table. A failing part doesn't keep the other one from
+// being solved, the first error is returned along with the answers.
+fn solve_day(puzzle: &Puzzle, input: &Path) -> ([String; 2], Option<String>) {
+    let first_line = |e: &dyn fmt::Display| e.to_string().lines().next().unwrap_or("").to_string();
+    let failed = || ["-".to_string(), "-".to_string()];
+
+    let text = match read_string(input) {
+        Ok(text) => text,
+        Err(e) => return (failed(), Some(first_line(&e))),
+    };
+    let parsed = match puzzle.parse(&text) {
+        Ok(parsed) => parsed,
+        Err(e) => return (failed(), Some(first_line(&e.in_file(input)))),
+    };
+
+    let mut error = None;
+    let answers = [1, 2].map(|part| match puzzle.part(&parsed, part) {
+        Ok(answer) => escape(&answer),
+        Err(e) => {
+            error.get_or_insert(first_line(&e));
+            "-".to_string()
+        }
+    });
+
+    (answers, error)
+}
+
+thread_local! {
+    // Set while the thread runs `catch_quietly`.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, catching its panic without printing it, so the caller can report it in its own way.
+/// Panics on other threads still go through the hook that was installed before.
+pub fn catch_quietly<R>(f: impl FnOnce() -> R) -> thread::Result<R> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                hook(info);
+            }
+        }));
+    });
+
+    let quiet = QUIET.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.set(quiet);
+    result
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "no message".to_string(),
+        },
+    }
+}
+
+// Solves one day on a thread of its own. A day that runs past `timeout` is abandoned: threads
+// can't be stopped from the outside, so it keeps running until the process exits. That is as
+// soon as `main` returns, the thread isn't joined.
+fn run_day(
+    year: &'static Year,
+    puzzle: &'static Puzzle,
+    input: &Path,
+    timeout: Duration,
+) -> DayResult {
+    let mut result = DayResult {
+        year: year.year,
+        day: puzzle.day,
+        answers: ["-".to_string(), "-".to_string()],
+        status: Status::Missing,
+        elapsed: Duration::ZERO,
+    };
+
+    if !input.exists() {
+        return result;
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_path_buf();
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let start = Instant::now();
+            let solved = catch_quietly(|| solve_day(puzzle, &input));
+            // Nobody is listening any more if the day timed out.
+            let _ = sender.send((solved, start.elapsed()));
+        })
+        .unwrap();
+
+    match receiver.recv_timeout(timeout) {
+        Ok((Ok((answers, error)), elapsed)) => {
+            result.answers = answers;
+            result.status = error.map_or(Status::Ok, Status::Error);
+            result.elapsed = elapsed;
+        }
+        Ok((Err(payload), elapsed)) => {
+            result.status = Status::Panic(panic_message(payload));
+            result.elapsed = elapsed;
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            result.status = Status::Timeout;
+            result.elapsed = timeout;
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            result.status = Status::Panic("the thread died".to_string());
+        }
+    }
+
+    result
+}
+
+// Runs all `days` on `workers` threads, the results are in the same order as the days.
+fn run_days(
+    days: &[(&'static Year, &'static Puzzle)],
+    example: Option<u32>,
+    workers: usize,
+    timeout: Duration,
+) -> Vec<DayResult> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&(year, puzzle)) = days.get(idx) else {
+                    break;
+                };
+
+                let input = input_path(Path::new(year.input_dir), puzzle.day, example);
+                let result = run_day(year, puzzle, &input, timeout);
+                results.lock().unwrap().push((idx, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(idx, _)| *idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Solves every registered day, or those of `year`, on a pool of `workers` threads (one per core
+/// if not given) and prints a table of the answers. Panicking days and days taking longer than
+/// `timeout` are reported without stopping the others. Timed out days are left running in the
+/// background, they only stop when the process exits.
+pub fn run_all(
+    years: &[&'static Year],
+    year: Option<u32>,
+    example: Option<u32>,
+    workers: Option<usize>,
+    timeout: Duration,
+) -> Result<(), Box<dyn Error>> {
+    let days: Vec<_> = years
+        .iter()
+        .filter(|y| year.is_none_or(|year| y.year == year))
+        .flat_map(|&y| y.puzzles.iter().map(move |puzzle| (y, puzzle)))
+        .collect();
+    if days.is_empty() {
+        return Err("nothing to run".into());
+    }
+
+    let workers = match workers {
+        Some(0) => return Err("need at least one worker".into()),
+        Some(workers) => workers,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+
+    let start = Instant::now();
+    let results = run_days(&days, example, workers, timeout);
+
+    println!(
+        "{:<4}  {:>3}  {:<16}  {:<16}  {:>10}  status",
+        "year", "day", "part 1", "part 2", "time"
+    );
+    for result in &results {
+        println!(
+            "{:<4}  {:>3}  {:<16}  {:<16}  {:>10.2?}  {}",
+            result.year,
+            result.day,
+            result.answers[0],
+            result.answers[1],
+            result.elapsed,
+            result.status
+        );
+    }
+
+    let failed = results
+        .iter()
+        .filter(|r| !matches!(r.status, Status::Ok | Status::Missing))
+        .count();
+    let missing = results
+        .iter()
+        .filter(|r| matches!(r.status, Status::Missing))
+        .count();
+
+    println!();
+    println!(
+        "{} solved, {} failed, {} missing in {:.2?}",
+        results.len() - failed - missing,
+        failed,
+        missing,
+        start.elapsed()
+    );
+
+    if failed > 0 {
+        return Err(format!("{} days failed", failed).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+use aoc_common::error::AocError;
+#[cfg(test)]
+use aoc_common::solution::{Answer, Solution};
+#[cfg(test)]
+struct Fine;
+#[cfg(test)]
+struct Panics;
+#[cfg(test)]
+struct Slow;
+
+#[cfg(test)]
+impl Solution for Fine {
+    type Input = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        aoc_common::error::parse_at(input, input.trim())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok((input * 2).into())
+    }
+
+    fn part2(_: &Self::Input) -> Result<Answer, AocError> {
+        Err("not yet".into())
+    }
+}
+
+#[cfg(test)]
+impl Solution for Panics {
+    type Input = ();
+
+    fn parse(_: &str) -> Result<Self::Input, AocError> {
+        Ok(())
+    }
+
+    fn part1(_: &Self::Input) -> Result<Answer, AocError> {
+        todo!()
+    }
+
+    fn part2(_: &Self::Input) -> Result<Answer, AocError> {
+        Ok(1.into())
+    }
+}
+
+#[cfg(test)]
+impl Solution for Slow {
+    type Input = ();
+
+    fn parse(_: &str) -> Result<Self::Input, AocError> {
+        thread::sleep(Duration::from_secs(5));
+        Ok(())
+    }
+
+    fn part1(_: &Self::Input) -> Result<Answer, AocError> {
+        Ok(1.into())
+    }
+
+    fn part2(_: &Self::Input) -> Result<Answer, AocError> {
+        Ok(1.into())
+    }
+}
+
+#[test]
+fn test_run_days() {
+    use std::fs;
+
+    const PUZZLES: &[Puzzle] = &[
+        Puzzle::new::<Fine>(1),
+        Puzzle::new::<Panics>(2),
+        Puzzle::new::<Slow>(3),
+        Puzzle::new::<Fine>(4),
+    ];
+
+    let dir = std::env::temp_dir().join(format!("aoc-run-all-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for day in 1..=3 {
+        fs::write(input_path(&dir, day, None), "21\n").unwrap();
+    }
+
+    let year: &'static Year = Box::leak(Box::new(Year {
+        year: 2000,
+        input_dir: Box::leak(dir.to_string_lossy().into_owned().into_boxed_str()),
+        puzzles: PUZZLES,
+    }));
+    let days: Vec<_> = year.puzzles.iter().map(|puzzle| (year, puzzle)).collect();
+
+    let results = run_days(&days, None, 2, Duration::from_millis(200));
+
+    assert_eq!(
+        results.iter().map(|r| r.day).collect::<Vec<_>>(),
+        [1, 2, 3, 4]
+    );
+    assert_eq!(results[0].answers, ["42".to_string(), "-".to_string()]);
+    assert!(matches!(&results[0].status, Status::Error(e) if e == "not yet"));
+    assert!(matches!(&results[1].status, Status::Panic(m) if m == "not yet implemented"));
+    assert!(matches!(results[2].status, Status::Timeout));
+    assert!(matches!(results[3].status, Status::Missing));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_catch_quietly() {
+    let result = catch_quietly(|| {
+        let inner = catch_quietly(|| panic!("inner"));
+        (panic_message(inner.unwrap_err()), QUIET.get())
+    });
+    assert_eq!(result.unwrap(), ("inner".to_string(), true));
+    assert!(!QUIET.get());
+}
@@ -0,0 +1,196 @@
+use aoc_common::input::{input_path, read_string};
+use aoc_common::solution::Year;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+
+// Every year starts its days from the same template, `DayNN` is replaced by the day's name.
+const TEMPLATE: &str = include_str!("../../2022/src/day_template.rs");
+
+/// Creates the module for `day` from the template, registers it in the year's lib.rs and creates
+/// empty input and example files. Nothing that already exists is overwritten.
+pub fn new_day(years: &[&Year], year: u32, day: u32) -> Result<(), Box<dyn Error>> {
+    let year = years
+        .iter()
+        .find(|y| y.year == year)
+        .ok_or_else(|| format!("no crate for {}", year))?;
+    let input_dir = Path::new(year.input_dir);
+    let src_dir = input_dir
+        .parent()
+        .ok_or("the input directory has no parent")?;
+
+    let lib_path = src_dir.join("lib.rs");
+    let lib = register(&read_string(&lib_path)?, day)?;
+
+    let module_path = src_dir.join(format!("day{:02}.rs", day));
+    let mut module = match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&module_path)
+    {
+        Ok(module) => module,
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            return Err(format!("{} already exists", module_path.display()).into())
+        }
+        Err(e) => return Err(e.into()),
+    };
+    module.write_all(
+        TEMPLATE
+            .replace("DayNN", &format!("Day{:02}", day))
+            .as_bytes(),
+    )?;
+    println!("created {}", module_path.display());
+
+    fs::write(&lib_path, lib)?;
+    println!("registered day {} in {}", day, lib_path.display());
+
+    for path in [
+        input_path(input_dir, day, None),
+        input_path(input_dir, day, Some(1)),
+    ] {
+        if !path.exists() {
+            fs::write(&path, "")?;
+            println!("created {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+// Adds the `mod` declaration and the puzzle for `day` to the source of a year's lib.rs.
+fn register(lib: &str, day: u32) -> Result<String, String> {
+    let mod_line = format!("mod day{:02};", day);
+    let puzzle_line = format!("Puzzle::new::<day{:02}::Day{:02}>({}),", day, day, day);
+
+    if lib.lines().any(|line| line.trim() == mod_line) {
+        return Err(format!("day {} is already registered", day));
+    }
+
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+    insert_sorted(&mut lines, "mod day", &mod_line)?;
+    insert_sorted(&mut lines, "Puzzle::new::<day", &puzzle_line)?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// Inserts `new` among the lines starting with `prefix`, keeping them sorted and using the same
+// indentation. Zero padded day numbers sort correctly as text.
+fn insert_sorted(lines: &mut Vec<String>, prefix: &str, new: &str) -> Result<(), String> {
+    let matching: Vec<usize> = (0..lines.len())
+        .filter(|&idx| lines[idx].trim_start().starts_with(prefix))
+        .collect();
+    let last = *matching
+        .last()
+        .ok_or_else(|| format!("found no `{}` lines to add to", prefix))?;
+
+    let idx = matching
+        .iter()
+        .copied()
+        .find(|&idx| lines[idx].trim_start() > new)
+        .unwrap_or(last + 1);
+    let indent = lines[last].len() - lines[last].trim_start().len();
+    let line = format!("{}{}", &lines[last][..indent], new);
+    lines.insert(idx, line);
+
+    Ok(())
+}
+
+#[test]
+fn test_register() {
+    let lib = "mod day01;\nmod day03;\n\nconst PUZZLES: &[Puzzle] = &[\n    \
+               Puzzle::new::<day01::Day01>(1),\n    Puzzle::new::<day03::Day03>(3),\n];\n";
+
+    assert_eq!(
+        register(lib, 2).unwrap(),
+        "mod day01;\nmod day02;\nmod day03;\n\nconst PUZZLES: &[Puzzle] = &[\n    \
+         Puzzle::new::<day01::Day01>(1),\n    Puzzle::new::<day02::Day02>(2),\n    \
+         Puzzle::new::<day03::Day03>(3),\n];\n"
+    );
+    assert!(register(lib, 12).unwrap().contains("day03;\nmod day12;\n"));
+    assert!(register(lib, 3).is_err());
+}
+
+// Copies the workspace sources at `from` to `to`, leaving out build output.
+#[cfg(test)]
+fn copy_sources(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let path = entry.path();
+        if entry.file_type().unwrap().is_dir() {
+            if entry.file_name() != "target" {
+                copy_sources(&path, &to.join(entry.file_name()));
+            }
+        } else {
+            fs::copy(&path, to.join(entry.file_name())).unwrap();
+        }
+    }
+}
+
+// Removes the directory when dropped, also when the test fails.
+#[cfg(test)]
+struct TempDir(std::path::PathBuf);
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+// Slow and needs the dependencies offline, run it with `cargo test -- --ignored` after changing
+// the template or the build script.
+#[test]
+#[ignore = "copies the workspace and runs cargo on it"]
+fn test_new_day_builds() {
+    use std::process::Command;
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let temp = TempDir(std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id())));
+    let dir = &temp.0;
+    for member in ["common", "runner", "2021", "2022"] {
+        copy_sources(&root.join(member), &dir.join(member));
+    }
+    for name in ["Cargo.toml", "Cargo.lock"] {
+        if root.join(name).exists() {
+            fs::copy(root.join(name), dir.join(name)).unwrap();
+        }
+    }
+
+    let day = (1..=25)
+        .find(|&day| aoc2022::YEAR.puzzle(day).is_none())
+        .unwrap();
+    let year = Year {
+        input_dir: Box::leak(
+            dir.join("2022/src/input")
+                .to_string_lossy()
+                .into_owned()
+                .into_boxed_str(),
+        ),
+        ..aoc2022::YEAR
+    };
+    new_day(&[&year], 2022, day).unwrap();
+
+    // Shared between runs, so only the first one builds the dependencies.
+    let target_dir = root.join("target/scaffold");
+    for (command, args) in [
+        ("clippy", &["--all-targets", "--", "-D", "warnings"][..]),
+        ("test", &[][..]),
+    ] {
+        let output = Command::new(env!("CARGO"))
+            .args([command, "--offline", "-p", "aoc2022"])
+            .args(args)
+            .env("CARGO_TARGET_DIR", &target_dir)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "cargo {} failed:\n{}{}",
+            command,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use aoc_common::simulation::Stepper;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+use std::time::Duration;
+
+const HELP: &str = "→/l step  ←/h back  [N]→ [N]← jump N  [N]g go to step N  G end  \
+                    space play/pause  +/- speed  q quit";
+
+// What a key press asks for. Digits typed before a key are its count, like in vi.
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Forward(usize),
+    Back(usize),
+    Goto(usize),
+    End,
+    TogglePlay,
+    Faster,
+    Slower,
+    Quit,
+}
+
+#[derive(Default)]
+struct Controls {
+    count: Option<usize>,
+}
+
+impl Controls {
+    fn key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Option<Action> {
+        if let KeyCode::Char(c @ '0'..='9') = code {
+            let digit = c.to_digit(10).unwrap() as usize;
+            self.count = Some(
+                self.count
+                    .unwrap_or(0)
+                    .saturating_mul(10)
+                    .saturating_add(digit),
+            );
+            return None;
+        }
+
+        let count = self.count.take();
+        match code {
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+            KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
+            KeyCode::Right | KeyCode::Char('l') => Some(Action::Forward(count.unwrap_or(1))),
+            KeyCode::Left | KeyCode::Char('h') => Some(Action::Back(count.unwrap_or(1))),
+            KeyCode::PageDown => Some(Action::Forward(count.unwrap_or(1).saturating_mul(10))),
+            KeyCode::PageUp => Some(Action::Back(count.unwrap_or(1).saturating_mul(10))),
+            KeyCode::Char('g') | KeyCode::Home => Some(Action::Goto(count.unwrap_or(0))),
+            KeyCode::Char('G') | KeyCode::End => Some(Action::End),
+            KeyCode::Char(' ') | KeyCode::Char('p') => Some(Action::TogglePlay),
+            KeyCode::Char('+') => Some(Action::Faster),
+            KeyCode::Char('-') => Some(Action::Slower),
+            _ => None,
+        }
+    }
+}
+
+fn draw(
+    out: &mut impl Write,
+    title: &str,
+    stepper: &Stepper,
+    controls: &Controls,
+    playing: bool,
+) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let (width, height) = (usize::from(width), usize::from(height));
+
+    let last = stepper
+        .last()
+        .map_or_else(|| "?".to_string(), |last| last.to_string());
+    let mut status = format!("{}  step {}/{}", title, stepper.step(), last);
+    if playing {
+        status.push_str("  playing");
+    }
+    if let Some(count) = controls.count {
+        status.push_str(&format!("  {}", count));
+    }
+
+    let rendered = stepper.render();
+    let lines = [status, String::new()]
+        .into_iter()
+        .chain(rendered.lines().map(str::to_string))
+        .take(height.saturating_sub(1));
+
+    queue!(out, Clear(ClearType::All))?;
+    for (row, line) in lines.enumerate() {
+        let line: String = line.chars().take(width).collect();
+        queue!(out, MoveTo(0, row as u16), Print(line))?;
+    }
+    let help: String = HELP.chars().take(width).collect();
+    queue!(out, MoveTo(0, height.saturating_sub(1) as u16), Print(help))?;
+
+    out.flush()
+}
+
+fn event_loop(
+    out: &mut impl Write,
+    title: &str,
+    stepper: &mut Stepper,
+) -> Result<(), Box<dyn Error>> {
+    let mut controls = Controls::default();
+    let mut playing = false;
+    let mut delay = Duration::from_millis(200);
+
+    loop {
+        draw(out, title, stepper, &controls, playing)?;
+
+        if playing && !event::poll(delay)? {
+            stepper.forward(1)?;
+            playing = !stepper.is_over();
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            // Resizes and the like only need a redraw.
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match controls.key(key.code, key.modifiers) {
+            Some(Action::Forward(steps)) => stepper.forward(steps)?,
+            Some(Action::Back(steps)) => stepper.back(steps)?,
+            Some(Action::Goto(step)) => stepper.goto(step)?,
+            Some(Action::End) => stepper.goto(usize::MAX)?,
+            Some(Action::TogglePlay) => playing = !playing && !stepper.is_over(),
+            Some(Action::Faster) => delay = (delay / 2).max(Duration::from_millis(10)),
+            Some(Action::Slower) => delay = (delay * 2).min(Duration::from_secs(5)),
+            Some(Action::Quit) => return Ok(()),
+            None => {}
+        }
+    }
+}
+
+// Raw mode on the alternate screen, for as long as it lives. Dropping it, on a panic as well,
+// leaves the terminal usable again.
+struct FullScreen;
+
+impl FullScreen {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let screen = FullScreen;
+        execute!(out, EnterAlternateScreen, Hide)?;
+        Ok(screen)
+    }
+}
+
+impl Drop for FullScreen {
+    fn drop(&mut self) {
+        // Nothing left to do about a terminal that can't be restored.
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Shows `stepper` full screen and lets the user move through it until they quit.
+pub fn interactive(title: &str, stepper: &mut Stepper) -> Result<(), Box<dyn Error>> {
+    let mut out = io::stdout();
+    if !out.is_terminal() {
+        return Err("stepping through a simulation needs a terminal".into());
+    }
+
+    let _screen = FullScreen::enter(&mut out)?;
+    event_loop(&mut out, title, stepper)
+}
+
+#[test]
+fn test_controls() {
+    let mut controls = Controls::default();
+    let mut press = |code| controls.key(code, KeyModifiers::NONE);
+
+    assert_eq!(press(KeyCode::Right), Some(Action::Forward(1)));
+    assert_eq!(press(KeyCode::Char('2')), None);
+    assert_eq!(press(KeyCode::Char('5')), None);
+    assert_eq!(press(KeyCode::Left), Some(Action::Back(25)));
+    assert_eq!(press(KeyCode::Left), Some(Action::Back(1)));
+    assert_eq!(press(KeyCode::Char('g')), Some(Action::Goto(0)));
+    assert_eq!(press(KeyCode::Char('7')), None);
+    assert_eq!(press(KeyCode::Char('g')), Some(Action::Goto(7)));
+    assert_eq!(press(KeyCode::PageDown), Some(Action::Forward(10)));
+    for _ in 0..25 {
+        assert_eq!(press(KeyCode::Char('9')), None);
+    }
+    assert_eq!(press(KeyCode::PageUp), Some(Action::Back(usize::MAX)));
+    assert_eq!(press(KeyCode::Char('G')), Some(Action::End));
+    assert_eq!(press(KeyCode::Char(' ')), Some(Action::TogglePlay));
+    assert_eq!(press(KeyCode::Char('x')), None);
+    assert_eq!(press(KeyCode::Char('q')), Some(Action::Quit));
+    assert_eq!(
+        controls.key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+        Some(Action::Quit)
+    );
+}
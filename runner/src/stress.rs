@@ -0,0 +1,145 @@
+use crate::run_all::{catch_quietly, panic_message};
+use aoc_common::random::Rng;
+use aoc_common::solution::Puzzle;
+use std::error::Error;
+use std::ops::Range;
+
+/// Prints a random input for `puzzle`.
+pub fn generate(puzzle: &Puzzle, seed: u64, size: usize) -> Result<(), Box<dyn Error>> {
+    let input = puzzle
+        .generate(&mut Rng::new(seed), size)
+        .map_err(|e| format!("day {}: {}", puzzle.day, e))?;
+    print!("{}", input);
+
+    Ok(())
+}
+
+// Parses and solves `input`, comparing the answers with the reference solutions where the day has
+// them. Describes the first thing going wrong.
+fn check(puzzle: &Puzzle, input: &str) -> Result<(), String> {
+    let solved = catch_quietly(|| {
+        let parsed = puzzle.parse(input).map_err(|e| e.to_string())?;
+        for part in [1, 2] {
+            let answer = puzzle
+                .part(&parsed, part)
+                .map_err(|e| format!("part {}: {}", part, e))?;
+
+            if let Some(expected) = puzzle.reference(&parsed, part) {
+                let expected = expected.map_err(|e| format!("part {} reference: {}", part, e))?;
+                if answer != expected {
+                    return Err(format!(
+                        "part {}: {}, but the reference says {}",
+                        part, answer, expected
+                    ));
+                }
+            }
+        }
+        Ok(())
+    });
+
+    match solved {
+        Ok(result) => result,
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload))),
+    }
+}
+
+// Checks the inputs generated from `seeds`, printing the failures. Returns how many failed.
+fn check_seeds(puzzle: &Puzzle, seeds: Range<u64>, size: usize) -> Result<usize, Box<dyn Error>> {
+    let mut failed = 0;
+
+    for seed in seeds {
+        let input = puzzle
+            .generate(&mut Rng::new(seed), size)
+            .map_err(|e| format!("day {}: {}", puzzle.day, e))?;
+
+        if let Err(e) = check(puzzle, &input) {
+            println!("seed {}: {}", seed, e.lines().next().unwrap_or(""));
+            failed += 1;
+        }
+    }
+
+    Ok(failed)
+}
+
+/// Solves `runs` random inputs for `puzzle`, generated from `seed`, `seed + 1`, ..., and reports
+/// the seeds of those that failed to parse or solve, or whose answers differ from the reference
+/// solutions.
+pub fn stress(puzzle: &Puzzle, seed: u64, size: usize, runs: u64) -> Result<(), Box<dyn Error>> {
+    let failed = check_seeds(puzzle, seed..seed.saturating_add(runs), size)?;
+
+    println!("{} inputs, {} failed", runs, failed);
+    if failed > 0 {
+        return Err(format!("{} inputs failed", failed).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+use aoc_common::error::AocError;
+#[cfg(test)]
+use aoc_common::solution::{Answer, Solution};
+
+// Counts commas and fields, with a reference that forgets about empty fields.
+#[cfg(test)]
+struct Commas;
+
+#[cfg(test)]
+impl Solution for Commas {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(input.matches(',').count().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok((input.matches(',').count() + 1).into())
+    }
+
+    fn reference(input: &Self::Input, part: u32) -> Option<Result<Answer, AocError>> {
+        let answer = match part {
+            1 => input.chars().filter(|&c| c == ',').count(),
+            _ => input.split(',').filter(|field| !field.is_empty()).count(),
+        };
+        Some(Ok(answer.into()))
+    }
+}
+
+#[test]
+fn test_check() {
+    let puzzle = Puzzle::new::<Commas>(1);
+    assert!(check(&puzzle, "a,b").is_ok());
+    assert_eq!(
+        check(&puzzle, ",,").unwrap_err(),
+        "part 2: 3, but the reference says 0"
+    );
+}
+
+#[test]
+fn test_check_seeds() {
+    let generating = [(2021, 14), (2021, 16), (2022, 7), (2022, 8), (2022, 11)];
+
+    for (year, day) in generating {
+        let (_, puzzle) = aoc_common::solution::find(crate::YEARS, year, day).unwrap();
+        for size in [1, 5, 30] {
+            assert_eq!(
+                check_seeds(puzzle, 0..10, size).unwrap(),
+                0,
+                "{} day {}",
+                year,
+                day
+            );
+        }
+        assert_eq!(
+            puzzle.generate(&mut Rng::new(3), 5).unwrap(),
+            puzzle.generate(&mut Rng::new(3), 5).unwrap()
+        );
+    }
+
+    let (_, puzzle) = aoc_common::solution::find(crate::YEARS, 2022, 1).unwrap();
+    assert!(check_seeds(puzzle, 0..1, 5).is_err());
+}
@@ -0,0 +1,435 @@
+use crate::client::{Client, Config};
+use aoc_common::answers::read_answers;
+use aoc_common::input::{input_path, read_string};
+use aoc_common::solution::{self, Year};
+use regex::Regex;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // Submitted during the cooldown, the answer wasn't checked.
+    TooRecent,
+    // The part was already solved, or part 1 isn't yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    // How wrong guesses are written in the guesses file.
+    fn word(self) -> Option<&'static str> {
+        match self {
+            Verdict::Wrong => Some("wrong"),
+            Verdict::TooHigh => Some("high"),
+            Verdict::TooLow => Some("low"),
+            Verdict::Correct | Verdict::TooRecent | Verdict::WrongLevel => None,
+        }
+    }
+
+    fn from_word(word: &str) -> Option<Verdict> {
+        [Verdict::Wrong, Verdict::TooHigh, Verdict::TooLow]
+            .into_iter()
+            .find(|verdict| verdict.word() == Some(word))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Verdict::Correct => "That's the right answer!",
+            Verdict::Wrong => "That's not the right answer.",
+            Verdict::TooHigh => "That's not the right answer, it is too high.",
+            Verdict::TooLow => "That's not the right answer, it is too low.",
+            Verdict::TooRecent => "An answer was given too recently.",
+            Verdict::WrongLevel => "This part is either solved already or not unlocked yet.",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Response {
+    verdict: Verdict,
+    // How long to wait before the next answer is accepted.
+    wait: Option<Duration>,
+}
+
+// The text of an HTML page with all tags removed and whitespace collapsed.
+fn page_text(html: &str) -> String {
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    let text = tags.replace_all(html, " ");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_wait(text: &str) -> Option<Duration> {
+    // Answering during the cooldown: "You have 1m 30s left to wait."
+    let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    if let Some(caps) = left.captures(text) {
+        let minutes: u64 = caps.get(1).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+        let seconds: u64 = caps[2].parse().ok()?;
+        return Some(Duration::from_secs(minutes * 60 + seconds));
+    }
+
+    // A wrong answer: "Please wait one minute before trying again."
+    let again = Regex::new(r"wait (\w+) minutes? before trying again").unwrap();
+    let count = &again.captures(text)?[1];
+    let words = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    let minutes = match words.iter().position(|word| *word == count) {
+        Some(idx) => idx as u64 + 1,
+        None => count.parse().ok()?,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+impl Response {
+    fn parse(html: &str) -> Result<Response, String> {
+        let text = page_text(html);
+
+        let verdict = if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if text.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::TooRecent
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            let start: String = text.chars().take(200).collect();
+            return Err(format!("unexpected response: {}", start));
+        };
+
+        Ok(Response {
+            verdict,
+            wait: parse_wait(&text),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Guess {
+    name: String,
+    part: u32,
+    verdict: Verdict,
+    answer: String,
+}
+
+/// Wrong answers given so far and the end of the current cooldown, kept in the `guesses` file
+/// next to the answers.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Guesses {
+    guesses: Vec<Guess>,
+    // Seconds since the epoch.
+    cooldown_until: Option<u64>,
+}
+
+const GUESSES_HEADER: &str = "\
+# Written by `aoc submit`: wrong answers as `<input file> <part> <wrong|high|low> <answer>` and
+# the end of the cooldown in seconds since the epoch.
+";
+
+impl Guesses {
+    fn parse(input: &str) -> Result<Guesses, String> {
+        let mut guesses = Guesses::default();
+
+        for (idx, line) in input.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let malformed = || format!("malformed guess on line {}: {:?}", idx + 1, line);
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+
+            match fields[..] {
+                ["cooldown", until] => {
+                    guesses.cooldown_until = Some(until.parse().map_err(|_| malformed())?);
+                }
+                [name, part, verdict, answer] => guesses.guesses.push(Guess {
+                    name: name.to_string(),
+                    part: part.parse().map_err(|_| malformed())?,
+                    verdict: Verdict::from_word(verdict).ok_or_else(malformed)?,
+                    answer: answer.to_string(),
+                }),
+                _ => return Err(malformed()),
+            }
+        }
+
+        Ok(guesses)
+    }
+
+    fn load(path: &Path) -> Result<Guesses, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(Guesses::default());
+        }
+        Ok(
+            Guesses::parse(&read_string(path)?)
+                .map_err(|e| format!("{}: {}", path.display(), e))?,
+        )
+    }
+
+    // Why `answer` is certainly wrong, judging by the answers given before.
+    fn refuse(&self, name: &str, part: u32, answer: &str) -> Option<String> {
+        let number = answer.parse::<i64>().ok();
+
+        self.guesses
+            .iter()
+            .filter(|guess| guess.name == name && guess.part == part)
+            .find_map(|guess| {
+                let bound = guess.answer.parse::<i64>().ok();
+                match (guess.verdict, number.zip(bound)) {
+                    _ if guess.answer == answer => Some("it was already wrong".to_string()),
+                    (Verdict::TooHigh, Some((number, bound))) if number >= bound => {
+                        Some(format!("{} was already too high", bound))
+                    }
+                    (Verdict::TooLow, Some((number, bound))) if number <= bound => {
+                        Some(format!("{} was already too low", bound))
+                    }
+                    _ => None,
+                }
+            })
+    }
+
+    fn cooldown_left(&self, now: SystemTime) -> Option<Duration> {
+        let until = UNIX_EPOCH + Duration::from_secs(self.cooldown_until?);
+        until.duration_since(now).ok()
+    }
+}
+
+impl fmt::Display for Guesses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", GUESSES_HEADER)?;
+        if let Some(until) = self.cooldown_until {
+            writeln!(f, "cooldown {}", until)?;
+        }
+        for guess in &self.guesses {
+            writeln!(
+                f,
+                "{} {} {} {}",
+                guess.name,
+                guess.part,
+                guess.verdict.word().unwrap_or("wrong"),
+                guess.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn append_answer(path: &Path, name: &str, part: u32, answer: &str) -> Result<(), Box<dyn Error>> {
+    let missing_newline =
+        fs::read_to_string(path).is_ok_and(|s| !s.is_empty() && !s.ends_with('\n'));
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if missing_newline {
+        writeln!(file)?;
+    }
+    writeln!(file, "{} {} {}", name, part, answer)?;
+    Ok(())
+}
+
+// Submits `answer` unless the guesses rule it out, and records the outcome: a correct answer in
+// the answers file, a wrong one and any cooldown in the guesses file.
+fn submit_answer(
+    client: &Client,
+    input_dir: &Path,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Verdict, Box<dyn Error>> {
+    let name = format!("day{:02}", day);
+    let answers_path = input_dir.join("answers");
+    let guesses_path = input_dir.join("guesses");
+
+    if answers_path.exists() {
+        if let Some(accepted) = read_answers(&answers_path)?.get(&name, part) {
+            return Err(
+                format!("{} part {} was already accepted: {}", name, part, accepted).into(),
+            );
+        }
+    }
+
+    let mut guesses = Guesses::load(&guesses_path)?;
+    if let Some(reason) = guesses.refuse(&name, part, answer) {
+        return Err(format!("not submitting {}, {}", answer, reason).into());
+    }
+
+    if let Some(wait) = guesses.cooldown_left(SystemTime::now()) {
+        println!("waiting {}s for the cooldown to end", wait.as_secs() + 1);
+        thread::sleep(wait + Duration::from_secs(1));
+    }
+
+    let response = Response::parse(&client.submit(year, day, part, answer)?)?;
+
+    if let Some(wait) = response.wait {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        guesses.cooldown_until = Some((now + wait).as_secs());
+    }
+
+    match response.verdict {
+        Verdict::Correct => append_answer(&answers_path, &name, part, answer)?,
+        Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow => guesses.guesses.push(Guess {
+            name,
+            part,
+            verdict: response.verdict,
+            answer: answer.to_string(),
+        }),
+        Verdict::TooRecent | Verdict::WrongLevel => {}
+    }
+    fs::write(&guesses_path, guesses.to_string())?;
+
+    Ok(response.verdict)
+}
+
+/// Submits `answer`, or if none is given the answer computed for the real input, for `part` of
+/// `day`.
+pub fn submit(
+    years: &[&Year],
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: Option<String>,
+    base_url: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let (year_info, puzzle) = solution::find(years, year, day)
+        .ok_or_else(|| format!("no solution for {} day {}", year, day))?;
+    let input_dir = Path::new(year_info.input_dir);
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let input = input_path(input_dir, day, None);
+            let answer = puzzle
+                .solve(&read_string(&input)?, part)
+                .map_err(|e| e.in_file(&input))?
+                .to_string();
+            if answer.contains('\n') {
+                return Err(format!("the answer is a picture:\n{}", answer).into());
+            }
+            answer
+        }
+    };
+
+    let client = Client::from_config(&Config::load()?, base_url.as_deref())?;
+    println!("submitting {}", answer);
+    let verdict = submit_answer(&client, input_dir, year, day, part, &answer)?;
+    println!("{}", verdict);
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_response() {
+    let response = |html| Response::parse(html).unwrap();
+    let minutes = |n: u64| Some(Duration::from_secs(n * 60));
+
+    let correct = "<main><article><p>That's the right answer!  You are <em>one gold star</em> \
+                   closer to collecting enough star fruit.</p></article></main>";
+    assert_eq!(response(correct).verdict, Verdict::Correct);
+    assert_eq!(response(correct).wait, None);
+
+    let high = "<article><p>That's not the right answer; your answer is too high.  \
+                Please wait one minute before trying again.</p></article>";
+    assert_eq!(response(high).verdict, Verdict::TooHigh);
+    assert_eq!(response(high).wait, minutes(1));
+
+    let wrong = "<p>That's not the right answer.  Please wait 5 minutes before trying again.</p>";
+    assert_eq!(response(wrong).verdict, Verdict::Wrong);
+    assert_eq!(response(wrong).wait, minutes(5));
+
+    let recent = "<p>You gave an answer too recently; you have to wait after submitting an \
+                  answer before trying again.  You have 1m 5s left to wait.</p>";
+    assert_eq!(response(recent).verdict, Verdict::TooRecent);
+    assert_eq!(response(recent).wait, Some(Duration::from_secs(65)));
+
+    let level =
+        "<p>You don't seem to be solving the right level.  Did you already complete it?</p>";
+    assert_eq!(response(level).verdict, Verdict::WrongLevel);
+
+    assert!(Response::parse("<p>Please log in.</p>").is_err());
+}
+
+#[test]
+fn test_guesses() {
+    let guesses = Guesses::parse(
+        "# comment\ncooldown 100\nday01 1 high 50\nday01 1 low 10\nday01 2 wrong x y\n",
+    )
+    .unwrap();
+
+    assert_eq!(Guesses::parse(&guesses.to_string()).unwrap(), guesses);
+    assert_eq!(guesses.guesses[2].answer, "x y");
+    assert!(Guesses::parse("day01 1 close 50").is_err());
+
+    assert!(guesses.refuse("day01", 1, "50").is_some());
+    assert!(guesses.refuse("day01", 1, "70").is_some());
+    assert!(guesses.refuse("day01", 1, "-3").is_some());
+    assert!(guesses.refuse("day01", 1, "30").is_none());
+    assert!(guesses.refuse("day01", 2, "x y").is_some());
+    assert!(guesses.refuse("day01", 2, "70").is_none());
+
+    assert_eq!(
+        guesses.cooldown_left(UNIX_EPOCH + Duration::from_secs(40)),
+        Some(Duration::from_secs(60))
+    );
+    assert_eq!(
+        guesses.cooldown_left(UNIX_EPOCH + Duration::from_secs(140)),
+        None
+    );
+}
+
+#[test]
+fn test_submit() {
+    let (base_url, server) = crate::client::stub_server(vec![
+        (
+            200,
+            "<p>That's not the right answer; your answer is too low.</p>",
+        ),
+        (200, "<p>That's the right answer!</p>"),
+    ]);
+    let client = Client::new(&base_url, "abc");
+
+    let dir = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("answers"), "day01 1 7").unwrap();
+
+    assert!(submit_answer(&client, &dir, 2022, 1, 1, "7").is_err());
+    assert_eq!(
+        submit_answer(&client, &dir, 2022, 1, 2, "10").unwrap(),
+        Verdict::TooLow
+    );
+    // Refused without asking the server.
+    assert!(submit_answer(&client, &dir, 2022, 1, 2, "9").is_err());
+    assert_eq!(
+        submit_answer(&client, &dir, 2022, 1, 2, "12").unwrap(),
+        Verdict::Correct
+    );
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].starts_with("POST /2022/day/1/answer "));
+    assert!(requests[0].ends_with("level=2&answer=10"));
+
+    assert_eq!(
+        fs::read_to_string(dir.join("answers")).unwrap(),
+        "day01 1 7\nday01 2 12\n"
+    );
+    assert!(fs::read_to_string(dir.join("guesses"))
+        .unwrap()
+        .ends_with("day01 2 low 10\n"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
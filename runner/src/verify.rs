@@ -0,0 +1,181 @@
+use aoc_common::answers::{escape, read_answers, Answers};
+use aoc_common::error::AocError;
+use aoc_common::input::{example_paths, input_path, read_string};
+use aoc_common::solution::{Puzzle, Year};
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+enum Status {
+    Pass,
+    Fail { got: String, expected: String },
+    Error(String),
+    // No input file or no accepted answer on record.
+    Missing,
+}
+
+impl Status {
+    // Parse errors come with the offending line underneath, the table only has room for the
+    // location and message.
+    fn error(e: &AocError) -> Status {
+        Status::Error(e.to_string().lines().next().unwrap_or_default().to_string())
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { got, expected } => {
+                write!(f, "FAIL     got {}, expected {}", got, expected)
+            }
+            Status::Error(e) => write!(f, "ERROR    {}", e),
+            Status::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// The real input followed by all examples of `day`.
+fn inputs(dir: &Path, day: u32) -> Vec<PathBuf> {
+    let mut inputs = vec![input_path(dir, day, None)];
+    inputs.extend(example_paths(dir, day).into_iter().map(|(_, path)| path));
+    inputs
+}
+
+fn verify_input(puzzle: &Puzzle, answers: &Answers, path: &Path) -> Vec<Status> {
+    let name = path.file_name().unwrap().to_string_lossy();
+
+    let expected: Vec<_> = (1..=2).map(|part| answers.get(&name, part)).collect();
+    if !path.exists() || expected.iter().all(Option::is_none) {
+        return vec![Status::Missing, Status::Missing];
+    }
+
+    let parsed = match read_string(path)
+        .map_err(AocError::from)
+        .and_then(|input| puzzle.parse(&input).map_err(|e| e.in_file(path)))
+    {
+        Ok(parsed) => parsed,
+        Err(e) => return vec![Status::error(&e), Status::error(&e)],
+    };
+
+    (1..=2)
+        .zip(expected)
+        .map(|(part, expected)| match expected {
+            None => Status::Missing,
+            Some(expected) => match puzzle.part(&parsed, part) {
+                Ok(answer) if answers.check(&name, part, &answer) == Some(true) => Status::Pass,
+                Ok(answer) => Status::Fail {
+                    got: escape(&answer),
+                    expected: expected.to_string(),
+                },
+                Err(e) => Status::error(&e),
+            },
+        })
+        .collect()
+}
+
+/// Runs every registered day against its accepted answers and prints a table of the results.
+pub fn verify(years: &[&Year], year: Option<u32>) -> Result<(), Box<dyn Error>> {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    println!("year  day  input             part  status");
+
+    for y in years
+        .iter()
+        .filter(|y| year.is_none_or(|year| y.year == year))
+    {
+        let answers_path = Path::new(y.input_dir).join("answers");
+        let answers = if answers_path.exists() {
+            read_answers(&answers_path)?
+        } else {
+            Answers::default()
+        };
+
+        for puzzle in y.puzzles {
+            for path in inputs(Path::new(y.input_dir), puzzle.day) {
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+
+                for (part, status) in (1..=2).zip(verify_input(puzzle, &answers, &path)) {
+                    match status {
+                        Status::Pass => passed += 1,
+                        Status::Missing => missing += 1,
+                        Status::Fail { .. } | Status::Error(_) => failed += 1,
+                    }
+
+                    println!(
+                        "{:<4}  {:>3}  {:<16}  {:>4}  {}",
+                        y.year, puzzle.day, name, part, status
+                    );
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    if failed > 0 {
+        return Err(format!("{} answers did not match", failed).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+use aoc_common::solution::{Answer, Solution};
+
+// Doubles the number in part 1, adds one in part 2.
+#[cfg(test)]
+struct Double;
+
+#[cfg(test)]
+impl Solution for Double {
+    type Input = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        aoc_common::error::parse_at(input, input.trim())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok((input * 2).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok((input + 1).into())
+    }
+}
+
+#[test]
+fn test_verify_input() {
+    use std::fs;
+
+    let dir = std::env::temp_dir().join(format!("aoc-verify-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(input_path(&dir, 1, None), "21\n").unwrap();
+    fs::write(input_path(&dir, 1, Some(1)), "3\n").unwrap();
+    fs::write(input_path(&dir, 1, Some(2)), "x\n").unwrap();
+    fs::write(input_path(&dir, 1, Some(3)), "5\n").unwrap();
+
+    let answers = Answers::parse(
+        "day01 1 42\nday01 2 23\nday01_example 1 6\nday01_example2 1 0\nday02 1 1\n",
+    )
+    .unwrap();
+    let puzzle = Puzzle::new::<Double>(1);
+    let rows = |path: &Path| -> Vec<String> {
+        verify_input(&puzzle, &answers, path)
+            .iter()
+            .map(Status::to_string)
+            .collect()
+    };
+
+    let paths = inputs(&dir, 1);
+    assert_eq!(paths.len(), 4);
+    assert_eq!(rows(&paths[0]), ["pass", "FAIL     got 22, expected 23"]);
+    assert_eq!(rows(&paths[1]), ["pass", "missing"]);
+    assert!(rows(&paths[2]).iter().all(|row| row.starts_with("ERROR")));
+    // An input nobody has an answer for, and an answer without its input.
+    assert_eq!(rows(&paths[3]), ["missing", "missing"]);
+    assert_eq!(rows(&input_path(&dir, 2, None)), ["missing", "missing"]);
+
+    fs::remove_dir_all(dir).unwrap();
+}